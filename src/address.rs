@@ -1,7 +1,13 @@
 // address.rs - модуль для валидации XRP-адресов
-use base58::FromBase58;  // Добавлен импорт трейта
+use std::fmt;
+use std::str::FromStr;
+
 use thiserror::Error;    // Добавлен импорт для ошибок
 
+use crate::codec::{self, CodecError};
+use crate::network::Network;
+use crate::xaddress::{self, XAddress, XAddressError};
+
 /// Пользовательские ошибки валидации адреса
 #[derive(Error, Debug)]
 pub enum AddressError {
@@ -11,7 +17,7 @@ pub enum AddressError {
     #[error("Адрес слишком длинный")]
     TooLong,
     
-    #[error("Адрес должен начинаться с 'r'")]
+    #[error("Адрес должен начинаться с 'r', 'X' или 'T'")]
     InvalidPrefix,
     
     #[error("Некорректный формат Base58")]
@@ -22,6 +28,110 @@ pub enum AddressError {
     
     #[error("Некорректная длина данных")]
     InvalidDataLength,
+    
+    #[error("Неверный байт версии адреса")]
+    InvalidVersion,
+    
+    #[error("Некорректный X-адрес: {0}")]
+    InvalidXAddress(#[from] XAddressError),
+    
+    #[error("Указан секретный seed, а не адрес")]
+    SecretSeed,
+    
+    #[error("Указан публичный ключ, а не адрес")]
+    PublicKey,
+}
+
+/// Идентификатор аккаунта XRPL (20 байт)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AccountId([u8; 20]);
+
+impl AccountId {
+    /// Возвращает идентификатор в виде шестнадцатеричной строки (верхний регистр)
+    pub fn to_hex(self) -> String {
+        self.0.iter().map(|b| format!("{:02X}", b)).collect()
+    }
+    
+    /// Возвращает классический адрес `r...`
+    pub fn to_classic_address(self) -> String {
+        codec::encode_account_id(&self.0)
+    }
+}
+
+impl fmt::Display for AccountId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_classic_address())
+    }
+}
+
+/// Форма, в которой был указан адрес
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressKind {
+    /// Классический адрес `r...`
+    Classic,
+    /// X-адрес `X...`/`T...`
+    XAddress,
+}
+
+/// Сеть, для которой предназначен адрес
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressNetwork {
+    Mainnet,
+    Testnet,
+}
+
+impl fmt::Display for AddressNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AddressNetwork::Mainnet => f.write_str("mainnet"),
+            AddressNetwork::Testnet => f.write_str("testnet"),
+        }
+    }
+}
+
+/// Результат разбора адреса
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddressInfo {
+    /// Идентификатор аккаунта
+    pub account_id: AccountId,
+    /// Форма исходного адреса
+    pub kind: AddressKind,
+    /// Тег назначения (только для X-адресов)
+    pub tag: Option<u32>,
+    /// Сеть (известна только для X-адресов)
+    pub network: Option<AddressNetwork>,
+}
+
+impl AddressInfo {
+    /// Классический адрес `r...`
+    pub fn classic_address(&self) -> String {
+        self.account_id.to_classic_address()
+    }
+    
+    /// Шестнадцатеричное представление AccountID
+    pub fn hex(&self) -> String {
+        self.account_id.to_hex()
+    }
+    
+    /// X-адрес этого аккаунта. Для классического адреса сеть X-адреса берется из выбранной сети;
+    /// в сети custom она неизвестна, и X-адреса нет
+    pub fn x_address(&self, network: Network) -> Option<String> {
+        let address_network = self.network.or_else(|| network.address_network())?;
+        let x_address = XAddress {
+            classic_address: self.classic_address(),
+            tag: self.tag,
+            is_test: address_network == AddressNetwork::Testnet,
+        };
+        Some(x_address.encode())
+    }
+}
+
+impl FromStr for AddressInfo {
+    type Err = AddressError;
+    
+    fn from_str(address: &str) -> Result<Self, Self::Err> {
+        AddressValidator::new().parse(address)
+    }
 }
 
 /// Валидатор XRP-адресов
@@ -33,11 +143,40 @@ impl AddressValidator {
         AddressValidator
     }
     
-    /// Валидирует XRP-адрес
-    pub fn validate(&self, address: &str) -> Result<(), AddressError> {
+    /// Разбирает и валидирует XRP-адрес (классический или X-адрес)
+    pub fn parse(&self, address: &str) -> Result<AddressInfo, AddressError> {
         log::debug!("Начинаем валидацию адреса: {}", address);
         log::debug!("Длина адреса: {}", address.len());
         
+        // X-адреса имеют собственный формат и проверяются отдельно
+        if xaddress::is_x_address(address) {
+            let x_address = XAddress::decode(address)?;
+            log::debug!("X-адрес валиден! Классический адрес: {}, тег: {:?}", 
+                        x_address.classic_address, x_address.tag);
+            
+            let account_id = codec::decode_account_id(&x_address.classic_address)?;
+            return Ok(AddressInfo {
+                account_id: AccountId(account_id),
+                kind: AddressKind::XAddress,
+                tag: x_address.tag,
+                network: Some(if x_address.is_test {
+                    AddressNetwork::Testnet
+                } else {
+                    AddressNetwork::Mainnet
+                }),
+            });
+        }
+        
+        // Seed и публичные ключи тоже кодируются в Base58Check: подсказываем, что указано не то
+        if codec::decode_seed(address).is_ok() {
+            log::debug!("Вместо адреса указан seed");
+            return Err(AddressError::SecretSeed);
+        }
+        if codec::decode_public_key(address).is_ok() {
+            log::debug!("Вместо адреса указан публичный ключ");
+            return Err(AddressError::PublicKey);
+        }
+        
         // Шаг 1: Проверка длины адреса
         if address.len() < 25 {
             log::debug!("Адрес слишком короткий: {} символов", address.len());
@@ -56,61 +195,37 @@ impl AddressValidator {
             return Err(AddressError::InvalidPrefix);
         }
         
-        // Шаг 3: Декодирование Base58
+        // Шаг 3: Декодирование Base58Check с проверкой версии, длины и контрольной суммы
         log::debug!("Пытаемся декодировать Base58...");
-        let decoded_bytes = match address.from_base58() {
-            Ok(bytes) => {
-                log::debug!("Успешно декодировано! Длина: {}", bytes.len());
-                log::debug!("Декодированные байты: {:?}", &bytes[..std::cmp::min(bytes.len(), 10)]); // Покажем первые 10 байт
-                bytes
-            },
+        let account_id = match codec::decode_account_id(address) {
+            Ok(account_id) => {
+                log::debug!("Успешно декодировано! AccountID: {:02X?}", account_id);
+                AccountId(account_id)
+            }
             Err(e) => {
-                log::debug!("Ошибка декодирования Base58: {:?}", e);
-                return Err(AddressError::InvalidBase58);
+                log::debug!("Ошибка декодирования адреса: {}", e);
+                return Err(e.into());
             }
         };
         
-        // Шаг 4: Проверка длины декодированных данных
-        log::debug!("Общая длина декодированных данных: {}", decoded_bytes.len());
-        if decoded_bytes.len() < 5 {
-            log::debug!("Некорректная длина данных после декодирования: {}", decoded_bytes.len());
-            return Err(AddressError::InvalidDataLength);
-        }
-        
-        // Шаг 5: Проверка контрольной суммы - ВРЕМЕННО ОТКЛЮЧЕНА
-        let data_len = decoded_bytes.len();
-        let data_without_checksum = &decoded_bytes[..data_len - 4];
-        let checksum = &decoded_bytes[data_len - 4..];
-        
-        log::debug!("Данные без чек-суммы ({} байт): {:?}", data_without_checksum.len(), 
-                    &data_without_checksum[..std::cmp::min(data_without_checksum.len(), 10)]);
-        log::debug!("Чек-сумма из адреса: {:?}", checksum);
-        
-        // ВРЕМЕННО отключаем проверку чек-суммы для тестирования
-        /*
-        let expected_checksum = Self::calculate_checksum(data_without_checksum);
-        log::debug!("Ожидаемая чек-сумма: {:?}", expected_checksum);
-        
-        if checksum != expected_checksum {
-            log::debug!("Чек-сумма не совпадает! Получено: {:?}, ожидалось: {:?}", checksum, expected_checksum);
-            return Err(AddressError::InvalidChecksum);
-        }
-        */
-        
-        log::debug!("Адрес валиден! (проверка чек-суммы временно отключена)");
-        Ok(())
+        log::debug!("Адрес валиден!");
+        Ok(AddressInfo {
+            account_id,
+            kind: AddressKind::Classic,
+            tag: None,
+            network: None,
+        })
     }
-    
-    /// Вычисляет контрольную сумму для данных (временно не используется)
-    #[allow(dead_code)]
-    fn calculate_checksum(data: &[u8]) -> [u8; 4] {
-        use crc32fast::Hasher;
-        
-        let mut hasher = Hasher::new();
-        hasher.update(data);
-        let checksum = hasher.finalize();
-        
-        checksum.to_be_bytes()
+}
+
+impl From<CodecError> for AddressError {
+    fn from(error: CodecError) -> Self {
+        match error {
+            CodecError::Empty | CodecError::InvalidCharacter { .. } => AddressError::InvalidBase58,
+            CodecError::InvalidChecksum => AddressError::InvalidChecksum,
+            CodecError::TooShort(_) | CodecError::InvalidLength { .. } => AddressError::InvalidDataLength,
+            CodecError::InvalidVersion { .. } => AddressError::InvalidVersion,
+        }
     }
 }
 
@@ -119,4 +234,32 @@ impl Default for AddressValidator {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_seeds_and_public_keys() {
+        let validator = AddressValidator::new();
+        assert!(matches!(validator.parse("sn259rEFXrQrWyx3Q7XneWcwV6dfL"), Err(AddressError::SecretSeed)));
+        assert!(matches!(validator.parse("sEdTM1uX8pu2do5XvTnutH6HsouMaM2"), Err(AddressError::SecretSeed)));
+        assert!(matches!(
+            validator.parse("n9MXXueo837zYH36DvMc13BwHcqtfAWNJY5czWVbp7uYTj7x17TH"),
+            Err(AddressError::PublicKey)
+        ));
+        assert!(validator.parse("rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh").is_ok());
+    }
+    #[test]
+    fn x_address_follows_selected_network() {
+        let info = AddressValidator::new().parse("rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh").unwrap();
+        assert!(info.x_address(Network::Mainnet).unwrap().starts_with('X'));
+        assert!(info.x_address(Network::Testnet).unwrap().starts_with('T'));
+        assert!(info.x_address(Network::Devnet).unwrap().starts_with('T'));
+        assert_eq!(info.x_address(Network::Custom), None);
+
+        // Сеть, закодированная в самом X-адресе, важнее выбранной
+        let info = AddressValidator::new().parse("T719a5UwUCnEs54UsxG9CJYYDhwmFCqkr7wxCcNcfZ6p5GZ").unwrap();
+        assert!(info.x_address(Network::Mainnet).unwrap().starts_with('T'));
+    }
+}
@@ -0,0 +1,419 @@
+// amount.rs - суммы XRPL: XRP, выпущенные токены (IOU) и MPT
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::codec::decode_hex;
+
+/// Количество drops в одном XRP
+pub const DROPS_PER_XRP: u64 = 1_000_000;
+
+/// Ошибки разбора и арифметики сумм
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AmountError {
+    #[error("Некорректная сумма в drops: '{0}'")]
+    InvalidDrops(String),
+
+    #[error("Переполнение при вычислении суммы")]
+    Overflow,
+}
+
+/// Точная сумма XRP в drops (без потерь точности f64)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(try_from = "String", into = "String")]
+pub struct Drops(pub u64);
+
+impl Drops {
+    pub const ZERO: Drops = Drops(0);
+
+    pub fn checked_add(self, other: Drops) -> Result<Drops, AmountError> {
+        self.0.checked_add(other.0).map(Drops).ok_or(AmountError::Overflow)
+    }
+
+    pub fn checked_sub(self, other: Drops) -> Result<Drops, AmountError> {
+        self.0.checked_sub(other.0).map(Drops).ok_or(AmountError::Overflow)
+    }
+
+    pub fn checked_mul(self, factor: u64) -> Result<Drops, AmountError> {
+        self.0.checked_mul(factor).map(Drops).ok_or(AmountError::Overflow)
+    }
+
+    /// Вычитание с ограничением снизу нулем
+    pub fn saturating_sub(self, other: Drops) -> Drops {
+        Drops(self.0.saturating_sub(other.0))
+    }
+}
+
+impl FromStr for Drops {
+    type Err = AmountError;
+
+    /// Разбирает целое число drops; знаки, дробная часть и пробелы не допускаются
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(AmountError::InvalidDrops(s.to_string()));
+        }
+        s.parse::<u64>()
+            .map(Drops)
+            .map_err(|_| AmountError::InvalidDrops(s.to_string()))
+    }
+}
+
+impl TryFrom<String> for Drops {
+    type Error = AmountError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Drops> for String {
+    fn from(drops: Drops) -> Self {
+        drops.0.to_string()
+    }
+}
+
+impl fmt::Display for Drops {
+    /// Точное десятичное значение в XRP с 6 знаками после запятой
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{:06}", self.0 / DROPS_PER_XRP, self.0 % DROPS_PER_XRP)
+    }
+}
+
+/// Код валюты: трехбуквенный ISO-подобный код или 160-битный hex
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(transparent)]
+pub struct Currency(pub String);
+
+impl Currency {
+    /// Человекочитаемое имя валюты.
+    /// Hex-коды декодируются: стандартный формат (0x00 + 3 символа ASCII)
+    /// и нестандартный (ASCII-строка, дополненная нулями). LP-токены AMM
+    /// (первый байт 0x03) и прочие коды возвращаются как есть.
+    pub fn display_name(&self) -> String {
+        let code = &self.0;
+        if code.len() != 40 {
+            return code.clone();
+        }
+
+        let Some(bytes) = decode_hex(code) else {
+            return code.clone();
+        };
+
+        if bytes.iter().all(|&b| b == 0) {
+            return "XRP".to_string();
+        }
+
+        if bytes[0] == 0x00 {
+            // Стандартный формат: символы валюты в байтах 12..15, остальное - нули
+            let standard = &bytes[12..15];
+            let rest_is_zero = bytes[..12].iter().chain(&bytes[15..]).all(|&b| b == 0);
+            if rest_is_zero && standard.iter().all(|b| b.is_ascii_alphanumeric()) {
+                return String::from_utf8_lossy(standard).into_owned();
+            }
+            return code.clone();
+        }
+
+        if bytes[0] == 0x03 {
+            return format!("LP:{}", &code[2..10]);
+        }
+
+        // Нестандартный формат: ASCII-строка, дополненная нулями справа
+        let trimmed: Vec<u8> = bytes.iter().copied().take_while(|&b| b != 0).collect();
+        let padding_is_zero = bytes[trimmed.len()..].iter().all(|&b| b == 0);
+        if padding_is_zero && trimmed.iter().all(|b| b.is_ascii_graphic()) {
+            return String::from_utf8_lossy(&trimmed).into_owned();
+        }
+
+        code.clone()
+    }
+
+    pub fn is_xrp(&self) -> bool {
+        self.display_name() == "XRP"
+    }
+
+    /// Код валюты из пользовательского ввода: трехсимвольный код, 40-символьный hex
+    /// или имя длиной до 20 символов (кодируется в нестандартный hex-формат).
+    /// None для XRP и недопустимых кодов.
+    pub fn from_code(code: &str) -> Option<Currency> {
+        let currency = match code.len() {
+            3 if code.chars().all(|c| c.is_ascii_alphanumeric()) => Currency(code.to_string()),
+            40 if decode_hex(code).is_some() => Currency(code.to_ascii_uppercase()),
+            4..=20 if code.chars().all(|c| c.is_ascii_graphic()) => {
+                let hex: String = code.bytes().map(|b| format!("{:02X}", b)).collect();
+                Currency(format!("{:0<40}", hex))
+            }
+            _ => return None,
+        };
+        (!currency.is_xrp()).then_some(currency)
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.display_name())
+    }
+}
+
+/// Сумма в выпущенном токене (IOU)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct IssuedAmount {
+    pub currency: Currency,
+    pub issuer: String,
+    /// Десятичное значение в исходном виде (может быть в экспоненциальной записи)
+    pub value: String,
+}
+
+/// Сумма в Multi-Purpose Token (MPT)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MptAmount {
+    pub mpt_issuance_id: String,
+    /// Целое количество единиц токена
+    pub value: String,
+}
+
+/// Сумма XRPL в одном из трех представлений.
+/// XRP передается строкой drops, токены - объектом.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum Amount {
+    /// XRP в drops (1 XRP = 1 000 000 drops)
+    Xrp(Drops),
+    /// Выпущенный токен
+    Issued(IssuedAmount),
+    /// Multi-Purpose Token
+    Mpt(MptAmount),
+}
+
+impl Amount {
+    /// Значение без единиц измерения (для XRP - в XRP, а не drops)
+    pub fn value_string(&self) -> String {
+        match self {
+            Amount::Xrp(drops) => drops.to_string(),
+            Amount::Issued(issued) => issued.value.clone(),
+            Amount::Mpt(mpt) => mpt.value.clone(),
+        }
+    }
+
+    /// Значение в единицах протокола (для XRP - в drops), как в JSON-представлении суммы.
+    /// Используется в машиночитаемом выводе, чтобы единицы в CSV совпадали с JSON и YAML
+    pub fn raw_value(&self) -> String {
+        match self {
+            Amount::Xrp(drops) => drops.0.to_string(),
+            Amount::Issued(issued) => issued.value.clone(),
+            Amount::Mpt(mpt) => mpt.value.clone(),
+        }
+    }
+
+    /// Приближенное значение для расчета цен (для XRP - в XRP, а не drops)
+    pub fn to_f64(&self) -> Option<f64> {
+        match self {
+            Amount::Xrp(drops) => Some(drops.0 as f64 / DROPS_PER_XRP as f64),
+            Amount::Issued(issued) => issued.value.parse().ok(),
+            Amount::Mpt(mpt) => mpt.value.parse().ok(),
+        }
+    }
+
+    /// Актив, в котором выражена сумма
+    pub fn asset(&self) -> Asset {
+        match self {
+            Amount::Xrp(_) => Asset::Xrp,
+            Amount::Issued(issued) => Asset::Issued {
+                currency: issued.currency.clone(),
+                issuer: issued.issuer.clone(),
+            },
+            Amount::Mpt(mpt) => Asset::Mpt {
+                mpt_issuance_id: mpt.mpt_issuance_id.clone(),
+            },
+        }
+    }
+}
+
+/// Цена предложения на DEX: сколько `pays` просят за единицу `gets`.
+/// None для пустого предложения.
+pub fn price(gets: &Amount, pays: &Amount) -> Option<f64> {
+    let gets = gets.to_f64()?;
+    if gets == 0.0 {
+        return None;
+    }
+    Some(pays.to_f64()? / gets)
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.value_string(), self.asset())
+    }
+}
+
+/// Актив без суммы (поля Asset/Asset2 у транзакций AMM)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum Asset {
+    Mpt {
+        mpt_issuance_id: String,
+    },
+    Issued {
+        currency: Currency,
+        issuer: String,
+    },
+    /// `{"currency": "XRP"}`
+    #[serde(with = "xrp_asset")]
+    Xrp,
+}
+
+impl Asset {
+    /// Код актива: XRP, имя валюты или идентификатор MPT
+    pub fn code(&self) -> String {
+        match self {
+            Asset::Xrp => "XRP".to_string(),
+            Asset::Issued { currency, .. } => currency.display_name(),
+            Asset::Mpt { mpt_issuance_id } => mpt_issuance_id.clone(),
+        }
+    }
+
+    /// Эмитент токена (у XRP и MPT отсутствует)
+    pub fn issuer(&self) -> Option<&str> {
+        match self {
+            Asset::Issued { issuer, .. } => Some(issuer),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Asset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Asset::Xrp => f.write_str("XRP"),
+            Asset::Issued { currency, issuer } => write!(f, "{}.{}", currency, issuer),
+            Asset::Mpt { mpt_issuance_id } => write!(f, "MPT:{}", mpt_issuance_id),
+        }
+    }
+}
+
+/// (Де)сериализация XRP как объекта `{"currency": "XRP"}`
+mod xrp_asset {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::Currency;
+
+    #[derive(Serialize, Deserialize)]
+    struct XrpAsset {
+        currency: Currency,
+    }
+
+    pub fn serialize<S: Serializer>(serializer: S) -> Result<S::Ok, S::Error> {
+        XrpAsset { currency: Currency("XRP".to_string()) }.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<(), D::Error> {
+        let asset = XrpAsset::deserialize(deserializer)?;
+        if asset.currency.is_xrp() {
+            Ok(())
+        } else {
+            Err(serde::de::Error::custom("ожидалась валюта XRP без эмитента"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name(code: &str) -> String {
+        Currency(code.to_string()).display_name()
+    }
+
+    #[test]
+    fn parses_drops() {
+        assert_eq!("0".parse::<Drops>(), Ok(Drops::ZERO));
+        assert_eq!("1000000".parse::<Drops>(), Ok(Drops(DROPS_PER_XRP)));
+        assert_eq!("18446744073709551615".parse::<Drops>(), Ok(Drops(u64::MAX)));
+    }
+
+    #[test]
+    fn rejects_malformed_drops() {
+        for input in ["", "-1", "+1", "1.5", " 1", "1e6", "18446744073709551616"] {
+            assert_eq!(input.parse::<Drops>(), Err(AmountError::InvalidDrops(input.to_string())));
+        }
+    }
+
+    #[test]
+    fn formats_drops_as_xrp() {
+        assert_eq!(Drops(0).to_string(), "0.000000");
+        assert_eq!(Drops(1).to_string(), "0.000001");
+        assert_eq!(Drops(12_345_678).to_string(), "12.345678");
+        // Максимум предложения XRP без потери точности
+        assert_eq!(Drops(100_000_000_000_000_000).to_string(), "100000000000.000000");
+    }
+
+    #[test]
+    fn serializes_drops_as_string() {
+        assert_eq!(serde_json::to_string(&Drops(15)).unwrap(), "\"15\"");
+        assert_eq!(serde_json::from_str::<Drops>("\"15\"").unwrap(), Drops(15));
+        assert!(serde_json::from_str::<Drops>("15").is_err());
+    }
+
+    #[test]
+    fn checked_arithmetic() {
+        assert_eq!(Drops(10).checked_add(Drops(5)), Ok(Drops(15)));
+        assert_eq!(Drops(10).checked_sub(Drops(5)), Ok(Drops(5)));
+        assert_eq!(Drops(10).checked_mul(3), Ok(Drops(30)));
+        assert_eq!(Drops(u64::MAX).checked_add(Drops(1)), Err(AmountError::Overflow));
+        assert_eq!(Drops(5).checked_sub(Drops(10)), Err(AmountError::Overflow));
+        assert_eq!(Drops(u64::MAX).checked_mul(2), Err(AmountError::Overflow));
+        assert_eq!(Drops(5).saturating_sub(Drops(10)), Drops::ZERO);
+    }
+
+    #[test]
+    fn keeps_three_letter_codes() {
+        assert_eq!(name("USD"), "USD");
+    }
+
+    #[test]
+    fn decodes_standard_hex_codes() {
+        assert_eq!(name("0000000000000000000000005553440000000000"), "USD");
+    }
+
+    #[test]
+    fn decodes_nonstandard_hex_codes() {
+        assert_eq!(name("534F4C4F00000000000000000000000000000000"), "SOLO");
+    }
+
+    #[test]
+    fn shortens_lp_token_codes() {
+        assert_eq!(name("03930D02208264E2E40EC1B0C09E4DB96EE197B1"), "LP:930D0220");
+    }
+
+    #[test]
+    fn zero_code_is_xrp() {
+        let currency = Currency("0".repeat(40));
+        assert_eq!(currency.display_name(), "XRP");
+        assert!(currency.is_xrp());
+    }
+
+    #[test]
+    fn leaves_undecodable_codes_as_is() {
+        // Стандартный формат с мусором вне байтов 12..15
+        let code = "0000000000000000000000015553440000000000";
+        assert_eq!(name(code), code);
+        // Нестандартный формат с ненулевым байтом после завершающего нуля
+        let code = "534F4C4F00000000000000000000000000000001";
+        assert_eq!(name(code), code);
+        assert_eq!(name("not hex"), "not hex");
+    }
+
+    #[test]
+    fn raw_value_keeps_protocol_units() {
+        let xrp: Amount = serde_json::from_str("\"1500000\"").unwrap();
+        assert_eq!(xrp.raw_value(), "1500000");
+        assert_eq!(xrp.value_string(), "1.500000");
+
+        let token: Amount = serde_json::from_str(
+            r#"{"currency": "USD", "issuer": "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B", "value": "2.5"}"#,
+        )
+        .unwrap();
+        assert_eq!(token.raw_value(), "2.5");
+        assert_eq!(token.value_string(), "2.5");
+    }
+}
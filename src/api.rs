@@ -1,155 +1,651 @@
 // use reqwest::{Client, Error as ReqwestError};
+use futures::future;
+use futures::stream::{self, Stream, TryStreamExt};
 use reqwest::Client;
+use serde::Serialize;
 use serde_json::Value;
 use std::time::Duration;
+use tokio::sync::mpsc;
 
+use crate::address::AccountId;
+use crate::amount::Asset;
 use crate::models::{
-    AccountInfoRequest, 
-    AccountInfoResponse, 
-    AccountTxRequest, 
+    AccountInfoRequest,
+    AccountInfoResponse,
+    AccountLinesResponse,
+    AccountLinesResult,
+    AccountNftsResponse,
+    AccountObjectsResponse,
+    AccountOffer,
+    AccountOffersResponse,
+    AccountPageRequest,
+    AccountTxQuery,
+    AccountTxRequest,
     AccountTxResponse,
-    DisplayTransaction
+    AccountTxResult,
+    BookOffer,
+    BookOffersRequest,
+    BookOffersResponse,
+    ErrorResponse,
+    LedgerRequest,
+    LedgerResponse,
+    LedgerResult,
+    NfToken,
+    Reserves,
+    ServerInfo,
+    ServerInfoRequest,
+    ServerInfoResponse,
+    ServerStateRequest,
+    ServerStateResponse,
+    StreamMessage,
+    SubscribeRequest,
+    SubscribeResponse,
+    TransactionWrapper,
+    TrustLine,
+    TrustLinePage,
+    TxRequest,
+    TxResponse,
+    TxResult,
+    ValidatedLedgerState,
 };
+use crate::error::ViewerError;
+use crate::network::Network;
+use crate::pool::EndpointPool;
+use crate::retry::{RetryClass, RetryPolicy};
+use crate::rippled_error::RippledError;
+use crate::transport::{self, Transport};
 
-use anyhow::{Result, Context};
+type Result<T> = std::result::Result<T, ViewerError>;
+
+/// Размер страницы при постраничной загрузке истории
+const HISTORY_PAGE_SIZE: u32 = 200;
+
+/// Таймаут запроса к серверу
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Таймаут проверки состояния сервера
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Размер страницы для account_lines, account_offers, account_nfts и account_objects
+const ACCOUNT_PAGE_SIZE: u32 = 400;
 
 pub struct XrpApi {
-    client: Client,
-    base_url: String,
+    pool: EndpointPool,
+    /// Транспорт для каждого сервера пула (по индексу)
+    transports: Vec<Box<dyn Transport>>,
+    retry: RetryPolicy,
+}
+
+/// Действующая подписка на потоки сервера
+pub struct Subscription {
+    /// Сервер, на котором оформлена подписка
+    pub url: String,
+    /// Последний валидированный леджер на момент подписки
+    pub ledger_index: Option<u32>,
+    events: mpsc::UnboundedReceiver<Value>,
+}
+
+impl Subscription {
+    /// Следующее сообщение потока; None - соединение разорвано
+    pub async fn next_message(&mut self) -> Option<Result<StreamMessage>> {
+        let message = self.events.recv().await?;
+        Some(serde_json::from_value(message).map_err(ViewerError::from))
+    }
+}
+
+/// Состояние постраничного обхода account_tx
+struct HistoryCursor {
+    query: AccountTxQuery,
+    marker: Option<Value>,
+    remaining: Option<u32>,
+    finished: bool,
+}
+
+impl HistoryCursor {
+    fn new(query: AccountTxQuery) -> Self {
+        HistoryCursor {
+            remaining: query.limit,
+            query,
+            marker: None,
+            finished: false,
+        }
+    }
+    
+    /// Привязывает следующие страницы к диапазону леджеров первой страницы: иначе
+    /// "-1" на каждой странице означал бы новый последний леджер
+    fn pin_ledgers(&mut self, ledger_index_min: Option<u32>, ledger_index_max: Option<u32>) {
+        if let (Some(min), Some(max)) = (ledger_index_min, ledger_index_max) {
+            self.query.ledger_index_min = Some(i64::from(min));
+            self.query.ledger_index_max = Some(i64::from(max));
+        }
+    }
+    
+    /// Размер следующей страницы; `None`, если обход завершен
+    fn next_page_limit(&self) -> Option<u32> {
+        if self.finished || self.remaining == Some(0) {
+            return None;
+        }
+        Some(self.remaining.map_or(HISTORY_PAGE_SIZE, |r| r.min(HISTORY_PAGE_SIZE)))
+    }
+    
+    /// Учитывает полученную страницу: обрезает ее по остатку лимита и запоминает marker
+    fn advance<T>(&mut self, mut items: Vec<T>, marker: Option<Value>) -> Result<Vec<T>> {
+        if let Some(remaining) = self.remaining.as_mut() {
+            items.truncate(*remaining as usize);
+            *remaining -= items.len() as u32;
+        }
+        
+        self.marker = next_marker(&self.marker, marker)?;
+        self.finished = self.marker.is_none();
+        Ok(items)
+    }
 }
 
 impl XrpApi {
-    pub fn new() -> Result<Self> {
+    /// Клиент для пула серверов: запрос уходит на наиболее синхронизированный доступный
+    pub fn new(urls: Vec<String>) -> Result<Self> {
         let client = Client::builder()
-            .timeout(Duration::from_secs(10))
             .user_agent("xrp-viewer/0.1.0")
-            .build()
-            .context("Не удалось создать HTTP-клиент")?;
+            .build()?;
+        
+        log::debug!("Используются серверы rippled: {}", urls.join(", "));
         
+        let transports = urls.iter().map(|url| transport::for_url(&client, url)).collect();
         Ok(XrpApi {
-            client,
-            base_url: "https://s1.ripple.com:51234  ".to_string(),
+            pool: EndpointPool::new(urls),
+            transports,
+            retry: RetryPolicy::default(),
         })
     }
     
-    pub async fn get_account_info(&self, address: &str) -> Result<AccountInfoResponse> {
-        let request = AccountInfoRequest::new(address.to_string());
+    /// Заменяет политику повторов запросов
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+    
+    pub async fn get_account_info(&self, address: &AccountId) -> Result<AccountInfoResponse> {
+        let request = AccountInfoRequest::new(address.to_classic_address());
         
         log::debug!("Отправка запроса account_info для адреса: {}", address);
         
-        let response = self.client
-            .post(&self.base_url)
-            .json(&request)
-            .send()
-            .await
-            .context("Не удалось отправить запрос к API")?;
-        
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_else(|_| "Неизвестная ошибка".to_string());
-            log::error!("API вернул ошибку {}: {}", status, error_text);
-            anyhow::bail!("API вернул ошибку {}: {}", status, error_text);
-        }
-        
-        let account_info: AccountInfoResponse = response
-            .json()
-            .await
-            .context("Не удалось разобрать ответ API")?;
-        
-        if account_info.result.status != "success" {
-            log::error!("API вернул статус: {}", account_info.result.status);
-            anyhow::bail!("API вернул статус: {}", account_info.result.status);
-        }
+        let account_info: AccountInfoResponse = self.send_json_rpc_request(&request).await.map_err(not_found(RippledError::ActNotFound, "Кошелек не найден"))?;
         
         log::debug!("Получен ответ account_info для адреса: {}", address);
         Ok(account_info)
     }
     
-    pub async fn get_latest_transaction(&self, address: &str) -> Result<Option<DisplayTransaction>> {
-        let request = AccountTxRequest::new(address.to_string());
+    pub async fn get_latest_transaction(&self, address: &AccountId) -> Result<Option<TransactionWrapper>> {
+        let request = AccountTxRequest::new(address.to_classic_address());
         
         log::debug!("Отправка запроса account_tx для адреса: {}", address);
         
-        let response = self.client
-            .post(&self.base_url)
-            .json(&request)
-            .send()
-            .await
-            .context("Не удалось отправить запрос к API")?;
-        
-        if !response.status().is_success() {
-            let status = response.status();
-            log::error!("API вернул ошибку: {}", status);
-            anyhow::bail!("API вернул ошибку: {}", status);
+        let mut account_tx: AccountTxResponse = self.send_json_rpc_request(&request).await.map_err(not_found(RippledError::ActNotFound, "Кошелек не найден"))?;
+        
+        if account_tx.result.transactions.is_empty() {
+            log::debug!("У кошелька нет транзакций");
+            return Ok(None);
         }
         
-        let account_tx: AccountTxResponse = response
-            .json()
-            .await
-            .context("Не удалось разобрать ответ API")?;
+        let first_tx_wrapper = account_tx.result.transactions.swap_remove(0);
         
-        if account_tx.result.status != "success" {
-            log::error!("API вернул статус: {}", account_tx.result.status);
-            anyhow::bail!("API вернул статус: {}", account_tx.result.status);
+        log::debug!("Получена последняя транзакция для адреса: {}", address);
+        Ok(Some(first_tx_wrapper))
+    }
+    
+    /// Запрашивает одну страницу истории транзакций
+    pub async fn get_account_tx_page(
+        &self,
+        address: &AccountId,
+        query: &AccountTxQuery,
+        limit: u32,
+        marker: Option<Value>,
+    ) -> Result<AccountTxResult> {
+        let request = AccountTxRequest::page(address.to_classic_address(), query, limit, marker);
+        
+        log::debug!("Отправка запроса account_tx (limit {}) для адреса: {}", limit, address);
+        
+        let account_tx: AccountTxResponse = self.send_json_rpc_request(&request).await.map_err(not_found(RippledError::ActNotFound, "Кошелек не найден"))?;
+        
+        log::debug!("Получено транзакций: {}, есть продолжение: {}",
+                    account_tx.result.transactions.len(), account_tx.result.marker.is_some());
+        Ok(account_tx.result)
+    }
+    
+    /// Поток транзакций аккаунта с автоматическим переходом по маркеру `marker`
+    pub fn account_tx_stream<'a>(
+        &'a self,
+        address: &AccountId,
+        query: AccountTxQuery,
+    ) -> impl Stream<Item = Result<TransactionWrapper>> + 'a {
+        let address = *address;
+        let cursor = HistoryCursor::new(query);
+        
+        stream::try_unfold(cursor, move |mut cursor| {
+            async move {
+                let Some(page_limit) = cursor.next_page_limit() else {
+                    return Ok::<_, ViewerError>(None);
+                };
+                
+                let page = self
+                    .get_account_tx_page(&address, &cursor.query, page_limit, cursor.marker.clone())
+                    .await?;
+                
+                cursor.pin_ledgers(page.ledger_index_min, page.ledger_index_max);
+                let transactions = cursor.advance(page.transactions, page.marker)?;
+                Ok(Some((transactions, cursor)))
+            }
+        })
+        .map_ok(|transactions| stream::iter(transactions.into_iter().map(Ok)))
+        .try_flatten()
+    }
+    
+    /// Транзакция по хешу (метод tx)
+    pub async fn get_transaction(&self, hash: &str) -> Result<TxResult> {
+        let request = TxRequest::new(hash.to_string());
+        
+        log::debug!("Отправка запроса tx для хеша: {}", hash);
+        
+        let response: TxResponse = self.send_json_rpc_request(&request).await.map_err(not_found(RippledError::TxnNotFound, "Транзакция не найдена"))?;
+        Ok(response.result)
+    }
+    
+    /// Все линии доверия аккаунта (account_lines со всеми страницами)
+    pub async fn get_account_lines(&self, address: &AccountId) -> Result<Vec<TrustLine>> {
+        let mut lines = Vec::new();
+        let mut marker = None;
+        let mut ledger_index = None;
+        
+        loop {
+            let page = self.account_lines_page(address, marker.clone(), ledger_index).await?;
+            // Все страницы читаются из леджера первой страницы
+            ledger_index = ledger_index.or(page.ledger.index());
+            lines.extend(page.lines);
+            marker = next_marker(&marker, page.marker)?;
+            if marker.is_none() {
+                return Ok(lines);
+            }
         }
+    }
+    
+    /// Только первая страница линий доверия (до `ACCOUNT_PAGE_SIZE`) - для сводки в info
+    pub async fn get_account_lines_page(&self, address: &AccountId) -> Result<TrustLinePage> {
+        let page = self.account_lines_page(address, None, None).await?;
+        Ok(TrustLinePage {
+            truncated: page.marker.is_some(),
+            lines: page.lines,
+        })
+    }
+    
+    async fn account_lines_page(&self, address: &AccountId, marker: Option<Value>, ledger_index: Option<u32>) -> Result<AccountLinesResult> {
+        let request = AccountPageRequest::new("account_lines", address.to_classic_address(), Some(ACCOUNT_PAGE_SIZE), marker)
+            .at_ledger(ledger_index);
         
-        if account_tx.result.transactions.is_empty() {
-            log::debug!("У кошелька нет транзакций");
-            return Ok(None);
+        log::debug!("Отправка запроса account_lines для адреса: {}", address);
+        
+        let response: AccountLinesResponse = self.send_json_rpc_request(&request).await.map_err(not_found(RippledError::ActNotFound, "Кошелек не найден"))?;
+        Ok(response.result)
+    }
+    
+    /// Все открытые предложения аккаунта на DEX (account_offers со всеми страницами)
+    pub async fn get_account_offers(&self, address: &AccountId) -> Result<Vec<AccountOffer>> {
+        let mut offers = Vec::new();
+        let mut marker = None;
+        let mut ledger_index = None;
+        
+        loop {
+            let request = AccountPageRequest::new("account_offers", address.to_classic_address(), Some(ACCOUNT_PAGE_SIZE), marker.clone())
+                .at_ledger(ledger_index);
+            
+            log::debug!("Отправка запроса account_offers для адреса: {}", address);
+            
+            let response: AccountOffersResponse = self.send_json_rpc_request(&request).await.map_err(not_found(RippledError::ActNotFound, "Кошелек не найден"))?;
+            
+            ledger_index = ledger_index.or(response.result.ledger.index());
+            offers.extend(response.result.offers);
+            marker = next_marker(&marker, response.result.marker)?;
+            if marker.is_none() {
+                return Ok(offers);
+            }
         }
+    }
+    
+    /// Предложения книги заявок `taker_pays` -> `taker_gets` от лучшей цены (метод book_offers)
+    pub async fn get_book_offers(&self, taker_gets: &Asset, taker_pays: &Asset, limit: u32) -> Result<Vec<BookOffer>> {
+        let request = BookOffersRequest::new(taker_gets.clone(), taker_pays.clone(), limit);
         
-        let first_tx_wrapper = &account_tx.result.transactions[0];
-        let transaction = &first_tx_wrapper.tx;
+        log::debug!("Отправка запроса book_offers: {} за {}", taker_gets, taker_pays);
         
-        let display_tx = DisplayTransaction::from_transaction(transaction);
+        let response: BookOffersResponse = self.send_json_rpc_request(&request).await?;
+        Ok(response.result.offers)
+    }
+    
+    /// Все NFT аккаунта (account_nfts со всеми страницами)
+    pub async fn get_account_nfts(&self, address: &AccountId) -> Result<Vec<NfToken>> {
+        let mut nfts = Vec::new();
+        let mut marker = None;
+        let mut ledger_index = None;
         
-        log::debug!("Получена последняя транзакция для адреса: {}", address);
-        Ok(display_tx)
+        loop {
+            let request = AccountPageRequest::new("account_nfts", address.to_classic_address(), Some(ACCOUNT_PAGE_SIZE), marker.clone())
+                .at_ledger(ledger_index);
+            
+            log::debug!("Отправка запроса account_nfts для адреса: {}", address);
+            
+            let response: AccountNftsResponse = self.send_json_rpc_request(&request).await.map_err(not_found(RippledError::ActNotFound, "Кошелек не найден"))?;
+            
+            ledger_index = ledger_index.or(response.result.ledger.index());
+            nfts.extend(response.result.account_nfts);
+            marker = next_marker(&marker, response.result.marker)?;
+            if marker.is_none() {
+                return Ok(nfts);
+            }
+        }
     }
     
-    #[allow(dead_code)]
-    pub async fn send_json_rpc_request<T>(&self, request_body: &Value) -> Result<T>
+    /// Все объекты леджера, принадлежащие аккаунту (account_objects со всеми страницами)
+    pub async fn get_account_objects(&self, address: &AccountId, object_type: Option<&str>) -> Result<Vec<Value>> {
+        let mut objects = Vec::new();
+        let mut marker = None;
+        let mut ledger_index = None;
+        
+        loop {
+            let request = AccountPageRequest::new("account_objects", address.to_classic_address(), Some(ACCOUNT_PAGE_SIZE), marker.clone())
+                .with_object_type(object_type.map(str::to_string))
+                .at_ledger(ledger_index);
+            
+            log::debug!("Отправка запроса account_objects для адреса: {}", address);
+            
+            let response: AccountObjectsResponse = self.send_json_rpc_request(&request).await.map_err(not_found(RippledError::ActNotFound, "Кошелек не найден"))?;
+            
+            ledger_index = ledger_index.or(response.result.ledger.index());
+            objects.extend(response.result.account_objects);
+            marker = next_marker(&marker, response.result.marker)?;
+            if marker.is_none() {
+                return Ok(objects);
+            }
+        }
+    }
+    
+    /// Заголовок леджера по номеру или псевдониму ("validated", "closed", "current")
+    pub async fn get_ledger(&self, ledger_index: Value) -> Result<LedgerResult> {
+        let request = LedgerRequest::new(ledger_index);
+        
+        log::debug!("Отправка запроса ledger");
+        
+        let response: LedgerResponse = self.send_json_rpc_request(&request).await.map_err(not_found(RippledError::LgrNotFound, "Леджер не найден"))?;
+        Ok(response.result)
+    }
+    
+    /// Состояние сервера rippled (метод server_info)
+    pub async fn get_server_info(&self) -> Result<ServerInfo> {
+        let request = ServerInfoRequest::new();
+        
+        log::debug!("Отправка запроса server_info");
+        
+        let response: ServerInfoResponse = self.send_json_rpc_request(&request).await?;
+        Ok(response.result.info)
+    }
+    
+    /// Резервы сети по последнему валидированному леджеру (метод server_state, точные значения в drops).
+    /// None, если у сервера еще нет валидированного леджера.
+    pub async fn get_reserves(&self) -> Result<Option<Reserves>> {
+        let request = ServerStateRequest::new();
+        
+        log::debug!("Отправка запроса server_state");
+        
+        let response: ServerStateResponse = self.send_json_rpc_request(&request).await?;
+        Ok(response.result.state.validated_ledger.as_ref().map(ValidatedLedgerState::reserves))
+    }
+    
+    /// Подписка на закрытие леджеров и транзакции счетов `addresses` (только WebSocket).
+    /// Без повторов: после сбоя или разрыва соединения подписку оформляют заново.
+    pub async fn subscribe(&self, addresses: &[AccountId]) -> Result<Subscription> {
+        let request = SubscribeRequest::new(addresses.iter().map(|address| address.to_classic_address()).collect());
+        
+        self.refresh_health().await;
+        let endpoint = self.pool.select();
+        log::debug!("Подписка на потоки сервера {}", self.pool.url(endpoint));
+        
+        // Канал открывается до запроса, чтобы не пропустить первые сообщения
+        let subscribe = async {
+            let events = self.transports[endpoint].events().await?;
+            let response: SubscribeResponse = self.send_json_rpc_request_to(endpoint, &request, REQUEST_TIMEOUT).await?;
+            Ok(Subscription {
+                url: self.pool.url(endpoint).to_string(),
+                ledger_index: response.result.ledger_index,
+                events,
+            })
+        };
+        subscribe.await.inspect_err(|error| self.record_failure(endpoint, error))
+    }
+    
+    /// Отправляет запрос JSON-RPC, повторяя его при временных сбоях согласно политике повторов.
+    /// Сервер с сетевым сбоем временно исключается из пула, и повтор уходит на другой.
+    pub async fn send_json_rpc_request<R, T>(&self, request_body: &R) -> Result<T>
     where
+        R: Serialize + ?Sized,
         T: serde::de::DeserializeOwned,
     {
-        let response = self.client
-            .post(&self.base_url)
-            .json(request_body)
-            .send()
-            .await
-            .context("Не удалось отправить запрос к API")?;
-        
-        if !response.status().is_success() {
-            let status = response.status();
-            log::error!("API вернул ошибку: {}", status);
-            anyhow::bail!("API вернул ошибку: {}", status);
+        let mut attempt = 1;
+        loop {
+            self.refresh_health().await;
+            let endpoint = self.pool.select();
+            
+            match self.send_json_rpc_request_to(endpoint, request_body, REQUEST_TIMEOUT).await {
+                Ok(result) => return Ok(result),
+                Err(error) => {
+                    self.record_failure(endpoint, &error);
+                    let class = error.retry_class();
+                    if !self.retry.should_retry(class, attempt) {
+                        return Err(error);
+                    }
+                    
+                    let class = class.expect("should_retry допускает повтор только для известного класса");
+                    let delay = self.retry.delay(attempt, class, error.retry_after());
+                    log::warn!("Попытка {}/{} не удалась: {}; повтор через {} мс", 
+                               attempt, self.retry.max_attempts, error, delay.as_millis());
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+    
+    /// Временно исключает сервер из пула после сетевого сбоя или перегрузки
+    fn record_failure(&self, endpoint: usize, error: &ViewerError) {
+        if self.pool.len() > 1 && matches!(error.retry_class(), Some(RetryClass::Network | RetryClass::ServerBusy)) {
+            self.pool.mark_down(endpoint);
+        }
+    }
+    
+    /// Проверяет серверы пула через server_info, если сведения о них устарели
+    async fn refresh_health(&self) {
+        if !self.pool.needs_health_check() {
+            return;
         }
         
-        let result: T = response
-            .json()
-            .await
-            .context("Не удалось разобрать ответ API")?;
+        let request = ServerInfoRequest::new();
+        let checks = (0..self.pool.len()).map(|endpoint| {
+            let request = &request;
+            async move {
+                let result: Result<ServerInfoResponse> = self
+                    .send_json_rpc_request_to(endpoint, request, HEALTH_CHECK_TIMEOUT)
+                    .await;
+                (endpoint, result)
+            }
+        });
+        
+        for (endpoint, result) in future::join_all(checks).await {
+            match result {
+                Ok(response) => {
+                    let validated_ledger = response.result.info.validated_ledger.map(|ledger| ledger.seq);
+                    log::debug!("Сервер {}: валидированный леджер {:?}", self.pool.url(endpoint), validated_ledger);
+                    self.pool.record_healthy(endpoint, validated_ledger);
+                }
+                Err(e) => {
+                    log::warn!("Сервер {} не прошел проверку состояния: {}", self.pool.url(endpoint), e);
+                    self.pool.record_unhealthy(endpoint);
+                }
+            }
+        }
+    }
+    
+    /// Одна попытка запроса к серверу пула через его транспорт; ошибка в ответе сервера
+    /// (`status: "error"`) возвращается как `ViewerError::Rpc`
+    async fn send_json_rpc_request_to<R, T>(&self, endpoint: usize, request_body: &R, timeout: Duration) -> Result<T>
+    where
+        R: Serialize + ?Sized,
+        T: serde::de::DeserializeOwned,
+    {
+        log::debug!("Запрос к серверу {}", self.pool.url(endpoint));
+        
+        let body = serde_json::to_value(request_body)?;
+        let value = self.transports[endpoint].request(body, timeout).await?;
+        
+        if value["result"]["status"] == "error" {
+            let response: ErrorResponse = serde_json::from_value(value)?;
+            log::debug!("Сервер вернул ошибку {:?}", response.result);
+            return Err(response.result.into());
+        }
         
-        Ok(result)
+        Ok(serde_json::from_value(value)?)
+    }
+}
+
+/// Заменяет ошибку RPC `kind` на `ViewerError::NotFound`
+fn not_found(kind: RippledError, message: &'static str) -> impl Fn(ViewerError) -> ViewerError {
+    move |error| match error {
+        ViewerError::Rpc { error, .. } if error == kind => ViewerError::NotFound(message.to_string()),
+        other => other,
     }
 }
 
+/// Marker следующей страницы. Сервер, вернувший тот же marker, зациклил бы постраничную загрузку
+fn next_marker(previous: &Option<Value>, next: Option<Value>) -> Result<Option<Value>> {
+    if next.is_some() && next == *previous {
+        return Err(ViewerError::Parse("сервер повторно вернул тот же marker".to_string()));
+    }
+    Ok(next)
+}
+
 impl Default for XrpApi {
     fn default() -> Self {
-        Self::new().expect("Не удалось создать API-клиент")
+        let urls = Network::Mainnet.default_urls().iter().map(|url| url.to_string()).collect();
+        Self::new(urls).expect("Не удалось создать API-клиент")
     }
 }
 
 #[allow(dead_code)]
-pub async fn is_account_exists(api: &XrpApi, address: &str) -> Result<bool> {
+pub async fn is_account_exists(api: &XrpApi, address: &AccountId) -> Result<bool> {
     match api.get_account_info(address).await {
         Ok(_) => Ok(true),
-        Err(e) => {
-            if e.to_string().contains("actNotFound") || e.to_string().contains("Account not found") {
-                Ok(false)
-            } else {
-                Err(e)
+        Err(ViewerError::NotFound(_)) => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn next_marker_detects_repeated_marker() {
+        assert_eq!(next_marker(&None, None).unwrap(), None);
+        assert_eq!(next_marker(&None, Some(json!("A"))).unwrap(), Some(json!("A")));
+        assert_eq!(next_marker(&Some(json!("A")), Some(json!("B"))).unwrap(), Some(json!("B")));
+        assert_eq!(next_marker(&Some(json!("A")), None).unwrap(), None);
+        assert!(matches!(next_marker(&Some(json!("A")), Some(json!("A"))), Err(ViewerError::Parse(_))));
+    }
+
+    #[test]
+    fn history_cursor_limit_spans_pages() {
+        let mut cursor = HistoryCursor::new(AccountTxQuery { limit: Some(250), ..AccountTxQuery::default() });
+        assert_eq!(cursor.next_page_limit(), Some(HISTORY_PAGE_SIZE));
+        
+        let page = cursor.advance(vec![0; 200], Some(json!("A"))).unwrap();
+        assert_eq!(page.len(), 200);
+        assert_eq!(cursor.marker, Some(json!("A")));
+        assert_eq!(cursor.next_page_limit(), Some(50));
+        
+        // Сервер может вернуть больше запрошенного - лишнее отбрасывается
+        let page = cursor.advance(vec![0; 60], Some(json!("B"))).unwrap();
+        assert_eq!(page.len(), 50);
+        assert_eq!(cursor.next_page_limit(), None);
+    }
+    
+    #[test]
+    fn history_cursor_stops_without_marker() {
+        let mut cursor = HistoryCursor::new(AccountTxQuery::default());
+        assert_eq!(cursor.next_page_limit(), Some(HISTORY_PAGE_SIZE));
+        
+        cursor.advance(vec![0; 200], Some(json!("A"))).unwrap();
+        assert_eq!(cursor.next_page_limit(), Some(HISTORY_PAGE_SIZE));
+        
+        let page = cursor.advance(vec![0; 10], None).unwrap();
+        assert_eq!(page.len(), 10);
+        assert_eq!(cursor.next_page_limit(), None);
+    }
+    
+    #[test]
+    fn history_cursor_rejects_repeated_marker() {
+        let mut cursor = HistoryCursor::new(AccountTxQuery::default());
+        cursor.advance(vec![0; 200], Some(json!("A"))).unwrap();
+        assert!(matches!(cursor.advance(vec![0; 200], Some(json!("A"))), Err(ViewerError::Parse(_))));
+    }
+    
+    #[test]
+    fn history_cursor_pins_first_page_ledgers() {
+        let query = AccountTxQuery { ledger_index_min: Some(-1), ledger_index_max: Some(-1), ..AccountTxQuery::default() };
+        let mut cursor = HistoryCursor::new(query);
+        cursor.pin_ledgers(Some(32_570), Some(90_000_000));
+        
+        let request = AccountTxRequest::page("rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh".to_string(), &cursor.query, 200, None);
+        let params = &serde_json::to_value(&request).unwrap()["params"][0];
+        assert_eq!(params["ledger_index_min"], 32_570);
+        assert_eq!(params["ledger_index_max"], 90_000_000);
+        
+        // Ответ без диапазона не сбрасывает уже зафиксированный
+        cursor.pin_ledgers(None, None);
+        assert_eq!(cursor.query.ledger_index_max, Some(90_000_000));
+    }
+    
+    /// Ошибка из ответа rippled, разобранная так же, как в `send_json_rpc_request_to`
+    fn rpc_error(response: Value) -> ViewerError {
+        let response: ErrorResponse = serde_json::from_value(response).unwrap();
+        response.result.into()
+    }
+    
+    #[test]
+    fn act_not_found_becomes_not_found() {
+        let error = rpc_error(json!({
+            "result": {
+                "account": "rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh",
+                "error": "actNotFound",
+                "error_code": 19,
+                "error_message": "Account not found.",
+                "status": "error"
             }
-        }
+        }));
+        assert!(matches!(error, ViewerError::Rpc { error: RippledError::ActNotFound, code: Some(19), .. }));
+        
+        let error = not_found(RippledError::ActNotFound, "Кошелек не найден")(error);
+        assert!(matches!(&error, ViewerError::NotFound(message) if message == "Кошелек не найден"));
+        assert_eq!(error.exit_code(), 3);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn not_found_keeps_other_errors() {
+        let error = rpc_error(json!({"result": {"error": "txnNotFound", "status": "error"}}));
+        let error = not_found(RippledError::ActNotFound, "Кошелек не найден")(error);
+        assert!(matches!(error, ViewerError::Rpc { error: RippledError::TxnNotFound, .. }));
+    }
+    
+    #[test]
+    fn unknown_error_code_is_kept() {
+        let error = rpc_error(json!({"result": {"error": "someNewError", "status": "error"}}));
+        assert!(matches!(&error, ViewerError::Rpc { error: RippledError::Other(code), .. } if code == "someNewError"));
+        assert_eq!(error.exit_code(), 5);
+        assert_eq!(error.retry_class(), None);
+    }
+}
@@ -0,0 +1,401 @@
+// codec.rs - кодирование и декодирование Base58 в алфавите XRPL и hex-полей
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+/// Алфавит Base58, используемый в XRPL
+const ALPHABET: &[u8; 58] = b"rpshnaf39wBUDNEGHJKLM4PQRST7VWXYZ2bcdeCg65jkm8oFqi1tuvAxyz";
+
+/// Длина контрольной суммы в байтах
+const CHECKSUM_LEN: usize = 4;
+
+/// Ошибки кодека Base58
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum CodecError {
+    #[error("Пустая строка")]
+    Empty,
+
+    #[error("Недопустимый символ '{ch}' в позиции {position}")]
+    InvalidCharacter { ch: char, position: usize },
+
+    #[error("Данные слишком короткие для контрольной суммы: {0} байт")]
+    TooShort(usize),
+
+    #[error("Неверная контрольная сумма")]
+    InvalidChecksum,
+
+    #[error("Неверный префикс версии: ожидался {expected:02X?}, получен {actual:02X?}")]
+    InvalidVersion { expected: Vec<u8>, actual: Vec<u8> },
+
+    #[error("Неверная длина данных: ожидалось {expected} байт, получено {actual}")]
+    InvalidLength { expected: usize, actual: usize },
+}
+
+/// Типы данных, кодируемых в Base58Check, с их префиксами версии
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadType {
+    /// Идентификатор аккаунта (адрес `r...`)
+    AccountId,
+    /// Публичный ключ узла (`n...`)
+    NodePublic,
+    /// Публичный ключ аккаунта (`a...`)
+    AccountPublic,
+    /// Seed secp256k1 (`s...`)
+    FamilySeed,
+    /// Seed Ed25519 (`sEd...`)
+    Ed25519Seed,
+}
+
+impl PayloadType {
+    /// Префикс версии, добавляемый перед данными
+    pub fn version(&self) -> &'static [u8] {
+        match self {
+            PayloadType::AccountId => &[0x00],
+            PayloadType::NodePublic => &[0x1C],
+            PayloadType::AccountPublic => &[0x23],
+            PayloadType::FamilySeed => &[0x21],
+            PayloadType::Ed25519Seed => &[0x01, 0xE1, 0x4B],
+        }
+    }
+
+    /// Ожидаемая длина данных без префикса и контрольной суммы
+    pub fn payload_len(&self) -> usize {
+        match self {
+            PayloadType::AccountId => 20,
+            PayloadType::NodePublic | PayloadType::AccountPublic => 33,
+            PayloadType::FamilySeed | PayloadType::Ed25519Seed => 16,
+        }
+    }
+}
+
+/// Кодирует байты в Base58 (без контрольной суммы)
+pub fn encode_base58(data: &[u8]) -> String {
+    let zeros = data.iter().take_while(|&&b| b == 0).count();
+
+    // Цифры в системе счисления по основанию 58, младшая первой
+    let mut digits: Vec<u8> = Vec::with_capacity(data.len() * 138 / 100 + 1);
+    for &byte in &data[zeros..] {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    let mut result = String::with_capacity(zeros + digits.len());
+    result.extend(std::iter::repeat_n(ALPHABET[0] as char, zeros));
+    result.extend(digits.iter().rev().map(|&d| ALPHABET[d as usize] as char));
+    result
+}
+
+/// Декодирует строку Base58 в байты (без проверки контрольной суммы)
+pub fn decode_base58(input: &str) -> Result<Vec<u8>, CodecError> {
+    if input.is_empty() {
+        return Err(CodecError::Empty);
+    }
+
+    let zeros = input.bytes().take_while(|&c| c == ALPHABET[0]).count();
+
+    // Байты результата, младший первым
+    let mut bytes: Vec<u8> = Vec::with_capacity(input.len() * 733 / 1000 + 1);
+    for (position, ch) in input.chars().enumerate().skip(zeros) {
+        let value = ALPHABET
+            .iter()
+            .position(|&c| c as char == ch)
+            .ok_or(CodecError::InvalidCharacter { ch, position })?;
+
+        let mut carry = value as u32;
+        for byte in bytes.iter_mut() {
+            carry += (*byte as u32) * 58;
+            *byte = (carry & 0xFF) as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push((carry & 0xFF) as u8);
+            carry >>= 8;
+        }
+    }
+
+    let mut result = vec![0u8; zeros];
+    result.extend(bytes.iter().rev());
+    Ok(result)
+}
+
+/// Вычисляет контрольную сумму: первые 4 байта двойного SHA-256
+pub fn checksum(data: &[u8]) -> [u8; CHECKSUM_LEN] {
+    let hash = Sha256::digest(Sha256::digest(data));
+
+    let mut result = [0u8; CHECKSUM_LEN];
+    result.copy_from_slice(&hash[..CHECKSUM_LEN]);
+    result
+}
+
+/// Кодирует данные в Base58Check: данные + контрольная сумма
+pub fn encode_check(data: &[u8]) -> String {
+    let mut buffer = data.to_vec();
+    buffer.extend_from_slice(&checksum(data));
+    encode_base58(&buffer)
+}
+
+/// Декодирует Base58Check и возвращает данные без контрольной суммы
+pub fn decode_check(input: &str) -> Result<Vec<u8>, CodecError> {
+    let mut bytes = decode_base58(input)?;
+    if bytes.len() <= CHECKSUM_LEN {
+        return Err(CodecError::TooShort(bytes.len()));
+    }
+
+    let data_len = bytes.len() - CHECKSUM_LEN;
+    if bytes[data_len..] != checksum(&bytes[..data_len]) {
+        return Err(CodecError::InvalidChecksum);
+    }
+
+    bytes.truncate(data_len);
+    Ok(bytes)
+}
+
+/// Кодирует типизированные данные с префиксом версии
+pub fn encode(kind: PayloadType, payload: &[u8]) -> Result<String, CodecError> {
+    if payload.len() != kind.payload_len() {
+        return Err(CodecError::InvalidLength {
+            expected: kind.payload_len(),
+            actual: payload.len(),
+        });
+    }
+
+    let mut data = kind.version().to_vec();
+    data.extend_from_slice(payload);
+    Ok(encode_check(&data))
+}
+
+/// Декодирует типизированные данные, проверяя префикс версии, длину и контрольную сумму
+pub fn decode(kind: PayloadType, input: &str) -> Result<Vec<u8>, CodecError> {
+    let data = decode_check(input)?;
+    let version = kind.version();
+
+    let expected_len = version.len() + kind.payload_len();
+    if data.len() != expected_len {
+        return Err(CodecError::InvalidLength {
+            expected: expected_len,
+            actual: data.len(),
+        });
+    }
+
+    if &data[..version.len()] != version {
+        return Err(CodecError::InvalidVersion {
+            expected: version.to_vec(),
+            actual: data[..version.len()].to_vec(),
+        });
+    }
+
+    Ok(data[version.len()..].to_vec())
+}
+
+/// Кодирует 20-байтовый AccountID в классический адрес `r...`
+pub fn encode_account_id(account_id: &[u8; 20]) -> String {
+    // Длина фиксирована типом, ошибка невозможна
+    encode(PayloadType::AccountId, account_id).expect("AccountID всегда 20 байт")
+}
+
+/// Декодирует классический адрес `r...` в 20-байтовый AccountID
+pub fn decode_account_id(address: &str) -> Result<[u8; 20], CodecError> {
+    let payload = decode(PayloadType::AccountId, address)?;
+
+    let mut account_id = [0u8; 20];
+    account_id.copy_from_slice(&payload);
+    Ok(account_id)
+}
+
+/// Декодирует seed, определяя его тип (Ed25519 или secp256k1)
+pub fn decode_seed(seed: &str) -> Result<(PayloadType, [u8; 16]), CodecError> {
+    let (kind, payload) = match decode(PayloadType::Ed25519Seed, seed) {
+        Ok(payload) => (PayloadType::Ed25519Seed, payload),
+        Err(CodecError::InvalidChecksum) => return Err(CodecError::InvalidChecksum),
+        Err(_) => (PayloadType::FamilySeed, decode(PayloadType::FamilySeed, seed)?),
+    };
+
+    let mut entropy = [0u8; 16];
+    entropy.copy_from_slice(&payload);
+    Ok((kind, entropy))
+}
+
+/// Декодирует публичный ключ узла (`n...`) или аккаунта (`a...`)
+pub fn decode_public_key(key: &str) -> Result<(PayloadType, [u8; 33]), CodecError> {
+    let (kind, payload) = match decode(PayloadType::NodePublic, key) {
+        Ok(payload) => (PayloadType::NodePublic, payload),
+        Err(CodecError::InvalidChecksum) => return Err(CodecError::InvalidChecksum),
+        Err(_) => (PayloadType::AccountPublic, decode(PayloadType::AccountPublic, key)?),
+    };
+
+    let mut public_key = [0u8; 33];
+    public_key.copy_from_slice(&payload);
+    Ok((kind, public_key))
+}
+
+/// Декодирует hex-строку в байты; None при нечетной длине или недопустимых символах
+pub fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| hex.get(i..i + 2).and_then(|byte| u8::from_str_radix(byte, 16).ok()))
+        .collect()
+}
+
+/// Декодирует hex-поле с текстом (Domain, URI, Memo): UTF-8 без управляющих символов
+pub fn decode_hex_text(hex: &str) -> Option<String> {
+    let text = String::from_utf8(decode_hex(hex)?).ok()?;
+    if text.chars().any(char::is_control) {
+        return None;
+    }
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Генезис-аккаунт и его AccountID
+    const GENESIS_ADDRESS: &str = "rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh";
+    const GENESIS_ACCOUNT_ID: [u8; 20] = [
+        0xB5, 0xF7, 0x62, 0x79, 0x8A, 0x53, 0xD5, 0x43, 0xA0, 0x14, 0xCA, 0xF8, 0xB2, 0x97, 0xCF, 0xF8, 0xF2, 0xF9,
+        0x37, 0xE8,
+    ];
+
+    #[test]
+    fn base58_keeps_leading_zeros() {
+        assert_eq!(encode_base58(&[0, 0, 1]), "rrp");
+        assert_eq!(decode_base58("rrp").unwrap(), vec![0, 0, 1]);
+    }
+
+    #[test]
+    fn base58_round_trip() {
+        let data: Vec<u8> = (0..=255).collect();
+        assert_eq!(decode_base58(&encode_base58(&data)).unwrap(), data);
+    }
+
+    #[test]
+    fn base58_rejects_characters_outside_alphabet() {
+        assert_eq!(decode_base58(""), Err(CodecError::Empty));
+        // '0', 'O', 'I' и 'l' в алфавит не входят
+        assert_eq!(
+            decode_base58("rp0"),
+            Err(CodecError::InvalidCharacter { ch: '0', position: 2 })
+        );
+    }
+
+    #[test]
+    fn encodes_known_account_ids() {
+        assert_eq!(encode_account_id(&GENESIS_ACCOUNT_ID), GENESIS_ADDRESS);
+        assert_eq!(encode_account_id(&[0; 20]), "rrrrrrrrrrrrrrrrrrrrrhoLvTp");
+
+        let mut account_one = [0; 20];
+        account_one[19] = 1;
+        assert_eq!(encode_account_id(&account_one), "rrrrrrrrrrrrrrrrrrrrBZbvji");
+    }
+
+    #[test]
+    fn decodes_known_account_ids() {
+        assert_eq!(decode_account_id(GENESIS_ADDRESS).unwrap(), GENESIS_ACCOUNT_ID);
+        assert_eq!(decode_account_id("rrrrrrrrrrrrrrrrrrrrrhoLvTp").unwrap(), [0; 20]);
+    }
+
+    #[test]
+    fn detects_corrupted_checksum() {
+        // Последний символ адреса изменен
+        assert_eq!(
+            decode_account_id("rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTi"),
+            Err(CodecError::InvalidChecksum)
+        );
+    }
+
+    #[test]
+    fn checks_payload_length() {
+        assert_eq!(
+            encode(PayloadType::AccountId, &[0; 19]),
+            Err(CodecError::InvalidLength { expected: 20, actual: 19 })
+        );
+        // Корректный Base58Check, но 21 байт вместо 1 + 20
+        let short = encode_check(&[0; 20]);
+        assert_eq!(
+            decode(PayloadType::AccountId, &short),
+            Err(CodecError::InvalidLength { expected: 21, actual: 20 })
+        );
+    }
+
+    #[test]
+    fn checks_version_prefix() {
+        let mut data = vec![0x23];
+        data.extend_from_slice(&GENESIS_ACCOUNT_ID);
+        assert_eq!(
+            decode(PayloadType::AccountId, &encode_check(&data)),
+            Err(CodecError::InvalidVersion { expected: vec![0x00], actual: vec![0x23] })
+        );
+    }
+
+    fn bytes<const N: usize>(hex: &str) -> [u8; N] {
+        decode_hex(hex).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn decodes_secp256k1_seeds() {
+        assert_eq!(
+            decode_seed("sn259rEFXrQrWyx3Q7XneWcwV6dfL").unwrap(),
+            (PayloadType::FamilySeed, bytes("CF2DE378FBDD7E2EE87D486DFB5A7BFF"))
+        );
+        assert_eq!(decode_seed("sp6JS7f14BuwFY8Mw6bTtLKWauoUs").unwrap(), (PayloadType::FamilySeed, [0; 16]));
+    }
+
+    #[test]
+    fn decodes_ed25519_seeds() {
+        assert_eq!(
+            decode_seed("sEdTM1uX8pu2do5XvTnutH6HsouMaM2").unwrap(),
+            (PayloadType::Ed25519Seed, bytes("4C3A1D213FBDFB14C7C28D609469B341"))
+        );
+        assert_eq!(decode_seed("sEdSJHS4oiAdz7w2X2ni1gFiqtbJHqE").unwrap(), (PayloadType::Ed25519Seed, [0; 16]));
+    }
+
+    #[test]
+    fn encodes_seeds() {
+        let entropy: [u8; 16] = bytes("CF2DE378FBDD7E2EE87D486DFB5A7BFF");
+        assert_eq!(encode(PayloadType::FamilySeed, &entropy).unwrap(), "sn259rEFXrQrWyx3Q7XneWcwV6dfL");
+        let entropy: [u8; 16] = bytes("4C3A1D213FBDFB14C7C28D609469B341");
+        assert_eq!(encode(PayloadType::Ed25519Seed, &entropy).unwrap(), "sEdTM1uX8pu2do5XvTnutH6HsouMaM2");
+    }
+
+    #[test]
+    fn rejects_non_seeds() {
+        assert_eq!(decode_seed("sn259rEFXrQrWyx3Q7XneWcwV6dfM"), Err(CodecError::InvalidChecksum));
+        assert!(decode_seed(GENESIS_ADDRESS).is_err());
+    }
+
+    #[test]
+    fn decodes_public_keys() {
+        const NODE_KEY: &str = "0388E5BA87A000CB807240DF8C848EB0B5FFA5C8E5A521BC8E105C0F0A44217828";
+        const ACCOUNT_KEY: &str = "023693F15967AE357D0327974AD46FE3C127113B1110D6044FD41E723689F81CC6";
+        assert_eq!(
+            decode_public_key("n9MXXueo837zYH36DvMc13BwHcqtfAWNJY5czWVbp7uYTj7x17TH").unwrap(),
+            (PayloadType::NodePublic, bytes(NODE_KEY))
+        );
+        assert_eq!(
+            decode_public_key("aB44YfzW24VDEJQ2UuLPV2PvqcPCSoLnL7y5M1EzhdW4LnK5xMS3").unwrap(),
+            (PayloadType::AccountPublic, bytes(ACCOUNT_KEY))
+        );
+        assert_eq!(
+            encode(PayloadType::NodePublic, &bytes::<33>(NODE_KEY)).unwrap(),
+            "n9MXXueo837zYH36DvMc13BwHcqtfAWNJY5czWVbp7uYTj7x17TH"
+        );
+        assert!(decode_public_key(GENESIS_ADDRESS).is_err());
+    }
+
+    #[test]
+    fn decodes_hex_text() {
+        assert_eq!(decode_hex("00FFab"), Some(vec![0x00, 0xFF, 0xAB]));
+        assert_eq!(decode_hex("ABC"), None);
+        assert_eq!(decode_hex("ZZ"), None);
+        assert_eq!(decode_hex_text("6578616D706C652E636F6D").as_deref(), Some("example.com"));
+        // Управляющие символы - не текст
+        assert_eq!(decode_hex_text("0001"), None);
+    }
+}
@@ -0,0 +1,14 @@
+// balance.rs - только баланс кошелька
+use super::{AddressArgs, AppContext, CommandResult};
+use crate::models::DisplayBalance;
+
+pub async fn run(ctx: &AppContext, args: AddressArgs) -> CommandResult {
+    let address = ctx.parse_address(&args.address)?;
+    
+    let api_client = ctx.api()?;
+    let account_info = api_client.get_account_info(&address.account_id).await?;
+    
+    let account_data = &account_info.result.account_data;
+    let balance = DisplayBalance::from_account_data(&address, account_data);
+    ctx.emit_one(&balance, |formatter| formatter.display_balance_only(account_data))
+}
@@ -0,0 +1,59 @@
+// book.rs - снимок книги заявок DEX по паре активов
+use clap::Args;
+
+use super::{AppContext, CommandResult};
+use crate::amount::{Asset, Currency};
+use crate::error::ViewerError;
+use crate::models::DisplayBookLevel;
+
+#[derive(Debug, Args)]
+pub struct BookArgs {
+    /// Базовый актив: XRP или ВАЛЮТА.эмитент (например, USD.rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B)
+    pub base: String,
+    
+    /// Котируемый актив, в котором выражена цена: XRP или ВАЛЮТА.эмитент
+    pub quote: String,
+    
+    /// Число предложений на каждой стороне книги
+    #[clap(long, default_value_t = 20, value_parser = clap::value_parser!(u32).range(1..=400))]
+    pub limit: u32,
+}
+
+pub async fn run(ctx: &AppContext, args: BookArgs) -> CommandResult {
+    let base = parse_asset(ctx, &args.base)?;
+    let quote = parse_asset(ctx, &args.quote)?;
+    if base == quote {
+        return Err(ViewerError::Validation("Базовый и котируемый активы совпадают".to_string()));
+    }
+    
+    let api_client = ctx.api()?;
+    
+    // Asks продают базовый актив за котируемый, bids - покупают
+    let asks = api_client.get_book_offers(&base, &quote, args.limit).await?;
+    let bids = api_client.get_book_offers(&quote, &base, args.limit).await?;
+    
+    let levels = DisplayBookLevel::order_book(&asks, &bids);
+    ctx.emit_list(&levels, |formatter| formatter.display_order_book(&base, &quote, &levels))
+}
+
+/// `XRP` или `ВАЛЮТА.эмитент`; эмитент - классический или X-адрес
+fn parse_asset(ctx: &AppContext, input: &str) -> Result<Asset, ViewerError> {
+    if input.eq_ignore_ascii_case("XRP") {
+        return Ok(Asset::Xrp);
+    }
+    
+    let Some((code, issuer)) = input.split_once('.') else {
+        return Err(ViewerError::Validation(format!(
+            "Некорректный актив '{}': ожидалось XRP или ВАЛЮТА.эмитент",
+            input
+        )));
+    };
+    let currency = Currency::from_code(code)
+        .ok_or_else(|| ViewerError::Validation(format!("Некорректный код валюты '{}'", code)))?;
+    let issuer = ctx.parse_address(issuer)?;
+    
+    Ok(Asset::Issued {
+        currency,
+        issuer: issuer.classic_address(),
+    })
+}
@@ -0,0 +1,101 @@
+// history.rs - история транзакций кошелька
+use chrono::{DateTime, Utc};
+use clap::Args;
+use futures::StreamExt;
+
+use super::{AppContext, CommandResult};
+use crate::models::AccountTxQuery;
+use crate::output;
+use crate::time;
+
+#[derive(Debug, Args)]
+pub struct HistoryArgs {
+    /// Публичный XRP-адрес: классический ('r...') или X-адрес ('X...'/'T...')
+    pub address: String,
+    
+    /// Максимальное количество транзакций (по умолчанию - вся история)
+    #[clap(long)]
+    pub limit: Option<u32>,
+    
+    /// Минимальный индекс леджера
+    #[clap(long)]
+    pub ledger_min: Option<i64>,
+    
+    /// Максимальный индекс леджера
+    #[clap(long)]
+    pub ledger_max: Option<i64>,
+    
+    /// Выводить от старых транзакций к новым
+    #[clap(long)]
+    pub forward: bool,
+    
+    /// Только транзакции не раньше указанного момента (RFC 3339 или YYYY-MM-DD)
+    #[clap(long, value_parser = time::parse_datetime)]
+    pub since: Option<DateTime<Utc>>,
+    
+    /// Только транзакции раньше указанного момента (RFC 3339 или YYYY-MM-DD)
+    #[clap(long, value_parser = time::parse_datetime)]
+    pub until: Option<DateTime<Utc>>,
+}
+
+/// Полная история транзакций кошелька
+pub async fn run(ctx: &AppContext, args: HistoryArgs) -> CommandResult {
+    let address = ctx.parse_address(&args.address)?;
+    
+    let api_client = ctx.api()?;
+    
+    // Границы по времени в секундах от Ripple epoch (даты до 2000 года - без ограничения)
+    let since = args.since.as_ref().and_then(time::datetime_to_ripple);
+    let until = args.until.as_ref().and_then(time::datetime_to_ripple);
+    
+    // При фильтре по времени лимит относится к выведенным транзакциям, а не к загруженным
+    let time_filtered = since.is_some() || until.is_some();
+    let query = AccountTxQuery {
+        limit: if time_filtered { None } else { args.limit },
+        ledger_index_min: args.ledger_min,
+        ledger_index_max: args.ledger_max,
+        forward: args.forward,
+    };
+    
+    let mut writer = ctx.output.writer(true);
+    if writer.is_none() {
+        ctx.formatter.display_history_header(&address);
+    }
+    
+    let transactions = api_client.account_tx_stream(&address.account_id, query);
+    futures::pin_mut!(transactions);
+    
+    let mut count = 0;
+    while let Some(wrapper) = transactions.next().await {
+        let wrapper = wrapper?;
+        let date = wrapper.tx.date.unwrap_or(0);
+        
+        // История упорядочена по времени: за границей интервала можно остановиться
+        let before_since = since.is_some_and(|since| date < since);
+        let after_until = until.is_some_and(|until| date >= until);
+        if (before_since && !args.forward) || (after_until && args.forward) {
+            break;
+        }
+        if before_since || after_until {
+            continue;
+        }
+        
+        match writer.as_mut() {
+            Some(writer) => {
+                output::write_record(writer.as_mut(), &wrapper.display())?;
+            }
+            None => ctx.formatter.display_history_entry(&wrapper),
+        }
+        count += 1;
+        
+        if args.limit.is_some_and(|limit| count >= limit as usize) {
+            break;
+        }
+    }
+    
+    match writer.as_mut() {
+        Some(writer) => writer.finish()?,
+        None => ctx.formatter.display_history_footer(count),
+    }
+    Ok(())
+}
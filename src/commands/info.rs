@@ -0,0 +1,41 @@
+// info.rs - сводка по кошельку: баланс с резервом, токены и последняя транзакция
+use super::{AddressArgs, AppContext, CommandResult};
+use crate::models::DisplayAccountInfo;
+
+pub async fn run(ctx: &AppContext, args: AddressArgs) -> CommandResult {
+    let address = ctx.parse_address(&args.address)?;
+    
+    // Создаем клиент API
+    let api_client = ctx.api()?;
+    
+    // Получаем информацию о кошельке
+    let account_info = api_client.get_account_info(&address.account_id).await?;
+    log::info!("Получена информация о кошельке");
+    
+    // Резервы сети нужны для расчета доступного остатка
+    let reserves = api_client.get_reserves().await?;
+    if reserves.is_none() {
+        log::warn!("Сервер не сообщил резервы сети, доступный остаток не рассчитан");
+    }
+    
+    // Для сводки по токенам достаточно первой страницы линий доверия (полный список - подкоманда lines).
+    // Сводка необязательна: без нее остальная информация о кошельке все равно выводится
+    let lines = api_client
+        .get_account_lines_page(&address.account_id)
+        .await
+        .inspect_err(|e| log::warn!("Не удалось получить линии доверия: {}", e))
+        .ok();
+    
+    // Получаем последнюю транзакцию
+    let transaction = api_client.get_latest_transaction(&address.account_id).await?;
+    
+    // Выводим информацию
+    let account_data = &account_info.result.account_data;
+    let info = DisplayAccountInfo::from_account_data(&address, ctx.network.network, account_data)
+        .with_reserves(reserves.as_ref())?
+        .with_trust_lines(lines.as_ref())
+        .with_last_transaction(transaction.as_ref());
+    ctx.emit_one(&info, |formatter| {
+        formatter.display_account_info(&address, &info, transaction.as_ref())
+    })
+}
@@ -0,0 +1,32 @@
+// ledger.rs - заголовок леджера
+use clap::Args;
+use serde_json::Value;
+
+use super::{AppContext, CommandResult};
+use crate::models::DisplayLedger;
+
+#[derive(Debug, Args)]
+pub struct LedgerArgs {
+    /// Номер леджера или validated / closed / current
+    #[clap(default_value = "validated", value_parser = parse_ledger_index)]
+    pub ledger: Value,
+}
+
+/// Номер леджера передается числом, псевдонимы - строкой
+fn parse_ledger_index(input: &str) -> Result<Value, String> {
+    match input {
+        "validated" | "closed" | "current" => Ok(Value::from(input)),
+        _ => input
+            .parse::<u32>()
+            .map(Value::from)
+            .map_err(|_| "ожидался номер леджера или validated / closed / current".to_string()),
+    }
+}
+
+pub async fn run(ctx: &AppContext, args: LedgerArgs) -> CommandResult {
+    let api_client = ctx.api()?;
+    
+    let result = api_client.get_ledger(args.ledger).await?;
+    let ledger = DisplayLedger::from_ledger_result(&result);
+    ctx.emit_one(&ledger, |formatter| formatter.display_ledger(&ledger))
+}
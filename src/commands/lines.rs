@@ -0,0 +1,13 @@
+// lines.rs - линии доверия (токены) кошелька
+use super::{AddressArgs, AppContext, CommandResult};
+use crate::models::DisplayTrustLine;
+
+pub async fn run(ctx: &AppContext, args: AddressArgs) -> CommandResult {
+    let address = ctx.parse_address(&args.address)?;
+    
+    let api_client = ctx.api()?;
+    
+    let lines = api_client.get_account_lines(&address.account_id).await?;
+    let records: Vec<DisplayTrustLine> = lines.iter().map(DisplayTrustLine::from_trust_line).collect();
+    ctx.emit_list(&records, |formatter| formatter.display_trust_lines(&address, &lines))
+}
@@ -0,0 +1,140 @@
+// commands - подкоманды CLI; у каждой свои аргументы и функция run
+pub mod balance;
+pub mod book;
+pub mod history;
+pub mod info;
+pub mod ledger;
+pub mod lines;
+pub mod nfts;
+pub mod objects;
+pub mod offers;
+pub mod server;
+pub mod tx;
+pub mod validate;
+pub mod watch;
+
+use clap::Args;
+
+use crate::address::{AddressInfo, AddressValidator};
+use crate::api::XrpApi;
+use crate::display::DisplayFormatter;
+use crate::error::ViewerError;
+use crate::network::NetworkConfig;
+use crate::output::{self, OutputFormat, Record};
+use crate::retry::RetryPolicy;
+
+/// Результат выполнения подкоманды
+pub type CommandResult = Result<(), ViewerError>;
+
+/// Общие настройки подкоманд, заданные глобальными флагами
+pub struct AppContext {
+    pub network: NetworkConfig,
+    pub formatter: DisplayFormatter,
+    pub output: OutputFormat,
+    pub retry: RetryPolicy,
+}
+
+/// Аргументы подкоманд, работающих с одним кошельком
+#[derive(Debug, Args)]
+pub struct AddressArgs {
+    /// Публичный XRP-адрес: классический ('r...') или X-адрес ('X...'/'T...')
+    pub address: String,
+}
+
+impl AppContext {
+    /// Клиент API для серверов выбранной сети
+    pub fn api(&self) -> Result<XrpApi, ViewerError> {
+        Ok(XrpApi::new(self.network.urls.clone())?.with_retry_policy(self.retry.clone()))
+    }
+    
+    /// Клиент API для WebSocket-серверов сети (подписки на потоки)
+    pub fn subscription_api(&self) -> Result<XrpApi, ViewerError> {
+        if self.network.ws_urls.is_empty() {
+            return Err(ViewerError::Validation(
+                "для подписки нужен WebSocket-сервер: укажите --url ws://... или wss://...".to_string(),
+            ));
+        }
+        Ok(XrpApi::new(self.network.ws_urls.clone())?.with_retry_policy(self.retry.clone()))
+    }
+    
+    /// Разбирает адрес и проверяет его соответствие сети
+    pub fn parse_address(&self, raw_address: &str) -> Result<AddressInfo, ViewerError> {
+        log::debug!("Получен адрес: {}", raw_address);
+        
+        // Создаем валидатор адреса
+        let validator = AddressValidator::new();
+        
+        // Валидация адреса с подробным логированием
+        log::debug!("Начинаем валидацию адреса...");
+        let address = validator.parse(raw_address).inspect_err(|error| {
+            log::info!("Ошибка валидации адреса {}: {:?}", raw_address, error);
+        })?;
+        log::info!("Адрес {} прошел валидацию (AccountID: {})", raw_address, address.hex());
+        
+        // X-адрес должен соответствовать выбранной сети
+        self.network.check_address(&address)?;
+        
+        Ok(address)
+    }
+    
+    /// Выводит список записей в машиночитаемом формате или текстом через `display`
+    pub fn emit_list<R: Record>(&self, records: &[R], display: impl FnOnce(&DisplayFormatter)) -> CommandResult {
+        match self.output.writer(true) {
+            Some(mut writer) => {
+                for record in records {
+                    output::write_record(writer.as_mut(), record)?;
+                }
+                writer.finish()?;
+            }
+            None => display(&self.formatter),
+        }
+        Ok(())
+    }
+    
+    /// Выводит одну запись в машиночитаемом формате или текстом через `display`
+    pub fn emit_one<R: Record>(&self, record: &R, display: impl FnOnce(&DisplayFormatter)) -> CommandResult {
+        match self.output.writer(false) {
+            Some(mut writer) => {
+                output::write_record(writer.as_mut(), record)?;
+                writer.finish()?;
+            }
+            None => display(&self.formatter),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::Network;
+
+    fn context(network: Network) -> AppContext {
+        AppContext {
+            network: NetworkConfig::resolve(Some(network), Vec::new()).unwrap(),
+            formatter: DisplayFormatter::default(),
+            output: OutputFormat::default(),
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    #[test]
+    fn accepts_classic_address() {
+        let address = context(Network::Mainnet).parse_address("rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh").unwrap();
+        assert_eq!(address.classic_address(), "rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh");
+    }
+
+    #[test]
+    fn invalid_address_is_a_validation_error() {
+        // Подкоманда должна завершиться с кодом ошибки, а не молча выйти с успехом
+        let error = context(Network::Mainnet).parse_address("rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTi").unwrap_err();
+        assert_eq!(error.exit_code(), 2);
+    }
+
+    #[test]
+    fn rejects_x_address_of_another_network() {
+        let testnet_address = "T719a5UwUCnEs54UsxG9CJYYDhwmFCqkr7wxCcNcfZ6p5GZ";
+        assert!(context(Network::Mainnet).parse_address(testnet_address).is_err());
+        assert!(context(Network::Testnet).parse_address(testnet_address).is_ok());
+    }
+}
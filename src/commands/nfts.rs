@@ -0,0 +1,13 @@
+// nfts.rs - NFT кошелька
+use super::{AddressArgs, AppContext, CommandResult};
+use crate::models::DisplayNft;
+
+pub async fn run(ctx: &AppContext, args: AddressArgs) -> CommandResult {
+    let address = ctx.parse_address(&args.address)?;
+    
+    let api_client = ctx.api()?;
+    
+    let nfts = api_client.get_account_nfts(&address.account_id).await?;
+    let records: Vec<DisplayNft> = nfts.iter().map(DisplayNft::from_nftoken).collect();
+    ctx.emit_list(&records, |formatter| formatter.display_nfts(&address, &nfts))
+}
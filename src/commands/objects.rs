@@ -0,0 +1,25 @@
+// objects.rs - объекты леджера, принадлежащие кошельку
+use clap::Args;
+
+use super::{AppContext, CommandResult};
+use crate::models::DisplayLedgerObject;
+
+#[derive(Debug, Args)]
+pub struct ObjectsArgs {
+    /// Публичный XRP-адрес: классический ('r...') или X-адрес ('X...'/'T...')
+    pub address: String,
+    
+    /// Только объекты указанного типа (check, escrow, offer, state, ticket, nft_offer...)
+    #[clap(long = "type")]
+    pub object_type: Option<String>,
+}
+
+pub async fn run(ctx: &AppContext, args: ObjectsArgs) -> CommandResult {
+    let address = ctx.parse_address(&args.address)?;
+    
+    let api_client = ctx.api()?;
+    
+    let objects = api_client.get_account_objects(&address.account_id, args.object_type.as_deref()).await?;
+    let records: Vec<DisplayLedgerObject> = objects.iter().map(DisplayLedgerObject::from_value).collect();
+    ctx.emit_list(&records, |formatter| formatter.display_objects(&address, &records))
+}
@@ -0,0 +1,13 @@
+// offers.rs - открытые предложения кошелька на DEX
+use super::{AddressArgs, AppContext, CommandResult};
+use crate::models::DisplayOffer;
+
+pub async fn run(ctx: &AppContext, args: AddressArgs) -> CommandResult {
+    let address = ctx.parse_address(&args.address)?;
+    
+    let api_client = ctx.api()?;
+    
+    let offers = api_client.get_account_offers(&address.account_id).await?;
+    let records: Vec<DisplayOffer> = offers.iter().map(DisplayOffer::from_offer).collect();
+    ctx.emit_list(&records, |formatter| formatter.display_offers(&address, &offers))
+}
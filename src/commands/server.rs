@@ -0,0 +1,11 @@
+// server.rs - состояние сервера rippled
+use super::{AppContext, CommandResult};
+use crate::models::DisplayServerInfo;
+
+pub async fn run(ctx: &AppContext) -> CommandResult {
+    let api_client = ctx.api()?;
+    
+    let info = api_client.get_server_info().await?;
+    let record = DisplayServerInfo::from_server_info(&info);
+    ctx.emit_one(&record, |formatter| formatter.display_server_info(&info))
+}
@@ -0,0 +1,29 @@
+// tx.rs - транзакция по хешу
+use clap::Args;
+
+use super::{AppContext, CommandResult};
+use crate::models::DisplayTransactionDetails;
+
+#[derive(Debug, Args)]
+pub struct TxArgs {
+    /// Хеш транзакции (64 hex-символа)
+    #[clap(value_parser = parse_tx_hash)]
+    pub hash: String,
+}
+
+/// Проверяет формат хеша транзакции и приводит его к верхнему регистру
+fn parse_tx_hash(input: &str) -> Result<String, String> {
+    if input.len() == 64 && input.bytes().all(|b| b.is_ascii_hexdigit()) {
+        Ok(input.to_ascii_uppercase())
+    } else {
+        Err("ожидалось 64 hex-символа".to_string())
+    }
+}
+
+pub async fn run(ctx: &AppContext, args: TxArgs) -> CommandResult {
+    let api_client = ctx.api()?;
+    
+    let result = api_client.get_transaction(&args.hash).await?;
+    let record = DisplayTransactionDetails::from_tx_result(&result);
+    ctx.emit_one(&record, |formatter| formatter.display_transaction(&result, &record))
+}
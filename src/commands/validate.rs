@@ -0,0 +1,26 @@
+// validate.rs - проверка адреса без обращения к сети
+use super::{AddressArgs, AppContext, CommandResult};
+use crate::address::AddressValidator;
+use crate::error::ViewerError;
+use crate::models::DisplayAddress;
+
+pub async fn run(ctx: &AppContext, args: AddressArgs) -> CommandResult {
+    let validator = AddressValidator::new();
+    
+    let parsed = validator
+        .parse(&args.address)
+        .map_err(ViewerError::from)
+        .and_then(|address| {
+            ctx.network.check_address(&address)?;
+            Ok(address)
+        });
+    
+    let record = match &parsed {
+        Ok(address) => DisplayAddress::valid(&args.address, address, ctx.network.network),
+        Err(e) => DisplayAddress::invalid(&args.address, e.to_string()),
+    };
+    ctx.emit_one(&record, |formatter| formatter.display_address(&record))?;
+    
+    // Невалидный адрес - ошибка для кода завершения, хотя отчет уже выведен
+    parsed.map(|_| ())
+}
@@ -0,0 +1,259 @@
+// watch.rs - наблюдение за транзакциями кошельков через подписку на потоки сервера
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+use clap::Args;
+use futures::TryStreamExt;
+
+use super::{AppContext, CommandResult};
+use crate::address::{AccountId, AddressInfo};
+use crate::api::{Subscription, XrpApi};
+use crate::models::{AccountTxQuery, DisplayWatchEvent, StreamMessage, TransactionWrapper};
+use crate::output::{self, OutputWriter};
+use crate::retry::RetryClass;
+
+#[derive(Debug, Args)]
+pub struct WatchArgs {
+    /// Публичные XRP-адреса: классические ('r...') или X-адреса ('X...'/'T...')
+    #[clap(required = true)]
+    pub addresses: Vec<String>,
+}
+
+/// Подписывается на потоки ledger и accounts и выводит валидированные транзакции
+/// адресов до Ctrl+C. После разрыва соединения подписка восстанавливается,
+/// а пропущенные леджеры догружаются через account_tx.
+pub async fn run(ctx: &AppContext, args: WatchArgs) -> CommandResult {
+    let addresses = args
+        .addresses
+        .iter()
+        .map(|raw_address| ctx.parse_address(raw_address))
+        .collect::<Result<Vec<_>, _>>()?;
+    
+    let mut watcher = Watcher {
+        ctx,
+        api_client: ctx.subscription_api()?,
+        writer: ctx.output.writer(true),
+        addresses,
+        progress: LedgerProgress::default(),
+    };
+    
+    if watcher.writer.is_none() {
+        ctx.formatter.display_watch_header(&watcher.addresses);
+    }
+    
+    tokio::select! {
+        result = watcher.watch() => result?,
+        _ = tokio::signal::ctrl_c() => {}
+    }
+    
+    if let Some(writer) = watcher.writer.as_mut() {
+        writer.finish()?;
+    }
+    Ok(())
+}
+
+struct Watcher<'a> {
+    ctx: &'a AppContext,
+    api_client: XrpApi,
+    writer: Option<Box<dyn OutputWriter>>,
+    addresses: Vec<AddressInfo>,
+    progress: LedgerProgress,
+}
+
+/// Позиция наблюдения в потоке валидированных леджеров
+#[derive(Debug, Default)]
+struct LedgerProgress {
+    /// Последний леджер, о закрытии которого сообщил сервер. Сообщение ledgerClosed
+    /// приходит раньше транзакций леджера, поэтому этот леджер может быть обработан не полностью
+    last_ledger: Option<u32>,
+    /// Хеши выведенных транзакций из `last_ledger` и более поздних леджеров (хеш -> леджер)
+    seen: HashMap<String, u32>,
+}
+
+impl LedgerProgress {
+    /// Леджеры, которые нужно догрузить после переподключения к серверу с леджером `current_ledger`.
+    /// `last_ledger` входит в диапазон: соединение могло прерваться посреди его транзакций
+    fn backfill_range(&self, current_ledger: u32) -> Option<RangeInclusive<u32>> {
+        let last_ledger = self.last_ledger?;
+        (current_ledger >= last_ledger).then_some(last_ledger..=current_ledger)
+    }
+    
+    fn close_ledger(&mut self, ledger_index: u32) {
+        if self.last_ledger.is_none_or(|last_ledger| ledger_index > last_ledger) {
+            self.last_ledger = Some(ledger_index);
+            self.seen.retain(|_, ledger| *ledger >= ledger_index);
+        }
+    }
+    
+    /// Отмечает транзакцию как выведенную; false, если она уже выводилась
+    fn mark_seen(&mut self, hash: &str, ledger_index: Option<u32>) -> bool {
+        if self.seen.contains_key(hash) {
+            return false;
+        }
+        let ledger_index = ledger_index.or(self.last_ledger).unwrap_or_default();
+        self.seen.insert(hash.to_string(), ledger_index);
+        true
+    }
+}
+
+impl Watcher<'_> {
+    /// Цикл подписки: возвращается только при ошибке, которую повтор не исправит
+    async fn watch(&mut self) -> CommandResult {
+        let accounts: Vec<AccountId> = self.addresses.iter().map(|address| address.account_id).collect();
+        let mut failures = 0;
+        
+        loop {
+            // Сбой догрузки пропущенных леджеров обрабатывается как сбой подписки:
+            // `progress` не сдвигается, и после переподключения догрузка повторяется
+            let result = match self.api_client.subscribe(&accounts).await {
+                Ok(subscription) => self.follow(subscription).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(()) => failures = 0,
+                Err(e) if e.retry_class().is_none() => return Err(e),
+                Err(e) => {
+                    failures += 1;
+                    log::warn!("Подписка прервана: {}", e);
+                }
+            }
+            
+            let delay = self.ctx.retry.delay(failures.max(1), RetryClass::Network, None);
+            log::info!("Переподключение через {} мс", delay.as_millis());
+            tokio::time::sleep(delay).await;
+        }
+    }
+    
+    /// Обрабатывает сообщения подписки до разрыва соединения
+    async fn follow(&mut self, mut subscription: Subscription) -> CommandResult {
+        log::info!("Подписка оформлена на {}", subscription.url);
+        
+        // Транзакции недообработанного и закрытых за время переподключения леджеров
+        if let Some(current_ledger) = subscription.ledger_index {
+            if let Some(ledgers) = self.progress.backfill_range(current_ledger) {
+                self.backfill(ledgers).await?;
+            }
+            self.progress.close_ledger(current_ledger);
+        }
+        
+        while let Some(message) = subscription.next_message().await {
+            match message {
+                Ok(StreamMessage::LedgerClosed { ledger_index }) => self.progress.close_ledger(ledger_index),
+                Ok(StreamMessage::Transaction(event)) if event.validated => match event.into_wrapper() {
+                    Ok(wrapper) => self.print(&wrapper)?,
+                    Err(e) => log::warn!("Некорректная транзакция в потоке: {}", e),
+                },
+                Ok(_) => {}
+                Err(e) => log::warn!("Некорректное сообщение потока: {}", e),
+            }
+        }
+        
+        log::warn!("Соединение с {} потеряно", subscription.url);
+        Ok(())
+    }
+    
+    /// Догружает транзакции адресов из леджеров `ledgers`.
+    /// Запросы повторяются по политике повторов API; уже выведенные транзакции не дублируются.
+    async fn backfill(&mut self, ledgers: RangeInclusive<u32>) -> CommandResult {
+        log::info!("Загрузка пропущенных леджеров {}..{}", ledgers.start(), ledgers.end());
+        
+        let accounts: Vec<AccountId> = self.addresses.iter().map(|address| address.account_id).collect();
+        for account_id in &accounts {
+            let query = AccountTxQuery {
+                limit: None,
+                ledger_index_min: Some(i64::from(*ledgers.start())),
+                ledger_index_max: Some(i64::from(*ledgers.end())),
+                forward: true,
+            };
+            
+            let transactions: Vec<TransactionWrapper> =
+                self.api_client.account_tx_stream(account_id, query).try_collect().await?;
+            
+            for wrapper in &transactions {
+                self.print(wrapper)?;
+            }
+        }
+        Ok(())
+    }
+    
+    /// Выводит транзакцию для каждого затронутого ею наблюдаемого адреса (один раз)
+    fn print(&mut self, wrapper: &TransactionWrapper) -> CommandResult {
+        if !self.progress.mark_seen(&wrapper.tx.hash, wrapper.tx.ledger_index) {
+            return Ok(());
+        }
+        
+        for address in &self.addresses {
+            let classic_address = address.classic_address();
+            let affected = wrapper.tx.account == classic_address
+                || wrapper.tx.destination() == Some(classic_address.as_str())
+                || wrapper.meta.as_ref().is_some_and(|meta| meta.mentions(&classic_address));
+            if !affected {
+                continue;
+            }
+            
+            match self.writer.as_mut() {
+                Some(writer) => output::write_record(writer.as_mut(), &DisplayWatchEvent::new(address, wrapper))?,
+                None => self.ctx.formatter.display_watch_entry(address, wrapper),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_subscription_has_nothing_to_backfill() {
+        assert_eq!(LedgerProgress::default().backfill_range(100), None);
+    }
+
+    #[test]
+    fn backfill_after_drop_mid_ledger() {
+        let mut progress = LedgerProgress::default();
+        progress.close_ledger(99);
+        assert!(progress.mark_seen("A", Some(99)));
+
+        // ledgerClosed 100 приходит раньше транзакций леджера 100;
+        // соединение рвется после первой из них
+        progress.close_ledger(100);
+        assert!(progress.mark_seen("B", Some(100)));
+
+        // После переподключения к серверу на леджере 102 леджер 100 догружается заново
+        assert_eq!(progress.backfill_range(102), Some(100..=102));
+        assert!(!progress.mark_seen("B", Some(100)));
+        assert!(progress.mark_seen("C", Some(100)));
+        assert!(progress.mark_seen("D", Some(101)));
+        assert!(progress.mark_seen("E", Some(102)));
+        progress.close_ledger(102);
+
+        // Транзакции леджера 102 из потока после догрузки не дублируются
+        assert!(!progress.mark_seen("E", Some(102)));
+        assert!(progress.mark_seen("F", Some(102)));
+    }
+
+    #[test]
+    fn reconnect_within_same_ledger_backfills_it() {
+        let mut progress = LedgerProgress::default();
+        progress.close_ledger(100);
+        assert!(progress.mark_seen("A", Some(100)));
+        assert_eq!(progress.backfill_range(100), Some(100..=100));
+        assert!(!progress.mark_seen("A", Some(100)));
+        // Сервер, отстающий от предыдущего, не догружает ничего
+        assert_eq!(progress.backfill_range(99), None);
+    }
+
+    #[test]
+    fn closing_ledger_forgets_earlier_transactions() {
+        let mut progress = LedgerProgress::default();
+        progress.close_ledger(100);
+        progress.mark_seen("A", Some(100));
+        progress.mark_seen("B", None);
+        progress.close_ledger(101);
+        assert!(progress.seen.is_empty());
+        // Более старый ledgerClosed не сдвигает позицию назад
+        progress.close_ledger(90);
+        assert_eq!(progress.last_ledger, Some(101));
+    }
+}
@@ -1,38 +1,135 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::amount::{Amount, Asset, Drops};
+use crate::codec;
 use crate::models::{
     DisplayAccountInfo,
-    DisplayTransaction,
+    DisplayAddress,
+    DisplayLedger,
+    DisplayAffectedNode,
+    DisplayLedgerObject,
+    DisplayTransactionDetails,
     AccountData,
+    AccountOffer,
+    BookSide,
+    BookSummary,
+    DisplayBookLevel,
+    NfToken,
+    ServerInfo,
+    TransactionWrapper,
+    TrustLine,
+    TxResult,
 };
+use crate::time::TimeFormatter;
+use crate::meta::TransactionMeta;
+use crate::transaction::{Transaction, TransactionKind};
+use crate::address::AddressInfo;
+
+/// Флаг tfSellNFToken у NFTokenCreateOffer
+const TF_SELL_NFTOKEN: u32 = 0x0000_0001;
 
-pub struct DisplayFormatter;
+pub struct DisplayFormatter {
+    time: TimeFormatter,
+}
 
 impl DisplayFormatter {
     pub fn new() -> Self {
-        DisplayFormatter
+        Self::with_time_formatter(TimeFormatter::default())
+    }
+    
+    pub fn with_time_formatter(time: TimeFormatter) -> Self {
+        DisplayFormatter { time }
     }
     
     pub fn display_account_info(
         &self,
-        address: &str,
-        account_data: &AccountData,
-        transaction: Option<&DisplayTransaction>
+        address: &AddressInfo,
+        display_info: &DisplayAccountInfo,
+        transaction: Option<&TransactionWrapper>
     ) {
-        let display_info = DisplayAccountInfo::from_account_data(
-            address.to_string(), 
-            account_data
-        );
-        
+
         println!("Address: {}", self.format_address(&display_info.address));
-        println!("Balance: {:.6} XRP", display_info.balance_xrp);
+        if let Some(x_address) = &display_info.x_address {
+            println!("X-Address: {}", x_address);
+        }
+        if let Some(tag) = address.tag {
+            println!("Destination Tag: {}", tag);
+        }
+        println!("Balance: {} XRP", display_info.balance);
+        if let (Some(reserved), Some(available)) = (display_info.reserved, display_info.available) {
+            println!("Reserved: {} XRP (base + {} owned objects)", reserved, display_info.owner_count);
+            println!("Available: {} XRP", available);
+        }
+        println!("Sequence: {}", display_info.sequence);
+        let flags = display_info.account_flags.names();
+        println!("Flags: {}", if flags.is_empty() { "none".to_string() } else { flags.join(", ") });
+        if let Some(regular_key) = &display_info.regular_key {
+            println!("Regular Key: {}", self.format_address(regular_key));
+        }
+        if let Some(domain) = &display_info.domain {
+            println!("Domain: {}", domain);
+        }
+        if let Some(email_hash) = &display_info.email_hash {
+            println!("Email Hash: {}", email_hash);
+        }
+        if let Some(message_key) = &display_info.message_key {
+            println!("Message Key: {}", message_key);
+        }
+        if let Some(transfer_rate) = display_info.transfer_rate {
+            println!("Transfer Rate: {}", format_quality(transfer_rate));
+        }
+        if let Some(tick_size) = display_info.tick_size {
+            println!("Tick Size: {}", tick_size);
+        }
+        if let Some(minter) = &display_info.nftoken_minter {
+            println!("NFToken Minter: {}", self.format_address(minter));
+        }
+        if let Some(minted) = display_info.minted_nftokens {
+            println!("Minted NFTokens: {} (burned {})", minted, display_info.burned_nftokens.unwrap_or(0));
+        }
+        if let Some(ticket_count) = display_info.ticket_count {
+            println!("Tickets: {}", ticket_count);
+        }
+        if let Some(amm_id) = &display_info.amm_id {
+            println!("AMM ID: {}", self.format_hash(amm_id));
+        }
+        if let Some(previous_txn_id) = &display_info.previous_txn_id {
+            let ledger = display_info.previous_txn_lgr_seq.map(|seq| format!(" (ledger {})", seq)).unwrap_or_default();
+            println!("Last Modified By: {}{}", self.format_hash(previous_txn_id), ledger);
+        }
+        match display_info.trust_lines {
+            Some(trust_lines) => {
+                let more = if display_info.tokens_truncated { "+" } else { "" };
+                println!("Tokens: {}{} (trust lines: {}{})", display_info.tokens.len(), more, trust_lines, more);
+            }
+            None => println!("Tokens: unavailable"),
+        }
+        for token in &display_info.tokens {
+            println!(" {:>24} {:<10} {}", token.balance, token.currency, self.format_address(&token.issuer));
+        }
+        if display_info.tokens_truncated {
+            println!(" ... more trust lines not shown, see `xrp-viewer lines`");
+        }
         println!("Last Transaction:");
         
         match transaction {
-            Some(tx) => {
+            Some(transaction) => {
+                let tx = transaction.display();
                 println!(" Hash: {}", self.format_hash(&tx.hash));
-                println!(" Amount: {:.6} XRP", tx.amount_xrp);
-                println!(" Timestamp: {}", tx.timestamp);
+                println!(" Type: {}", tx.transaction_type);
+                match &tx.amount {
+                    Some(amount) => println!(" Amount: {}", self.format_amount(amount)),
+                    None if tx.partial_payment => println!(" Amount: unknown"),
+                    None => {}
+                }
+                println!(" Details: {}", self.format_transaction_summary(&transaction.tx, transaction.meta.as_ref()));
+                println!(" Timestamp: {}", self.format_datetime(tx.timestamp.as_ref()));
                 println!(" From: {}", self.format_address(&tx.from));
-                println!(" To: {}", self.format_address(&tx.to));
+                if let Some(to) = &tx.to {
+                    println!(" To: {}", self.format_address(to));
+                }
             }
             None => {
                 println!(" Нет транзакций.");
@@ -40,6 +137,488 @@ impl DisplayFormatter {
         }
     }
     
+    pub fn display_history_header(&self, address: &AddressInfo) {
+        println!("History: {}", address.classic_address());
+    }
+    
+    pub fn display_history_entry(&self, wrapper: &TransactionWrapper) {
+        let tx = &wrapper.tx;
+        println!(" {}  {:<20} {}  {}", 
+                 self.format_datetime(tx.datetime().as_ref()), 
+                 tx.transaction_type, 
+                 self.format_hash(&tx.hash), 
+                 self.format_transaction_summary(tx, wrapper.meta.as_ref()));
+    }
+    
+    /// Краткое описание транзакции в зависимости от её типа.
+    /// Для платежей показывается доставленная сумма из метаданных, если они есть;
+    /// у частичного платежа без нее сумма неизвестна.
+    pub fn format_transaction_summary(&self, tx: &Transaction, meta: Option<&TransactionMeta>) -> String {
+        match &tx.kind {
+            TransactionKind::Payment { destination, amount, destination_tag, .. } => {
+                let tag = destination_tag.map(|t| format!(" (tag {})", t)).unwrap_or_default();
+                let delivered = meta.and_then(TransactionMeta::delivered_amount);
+                let shown = match &delivered {
+                    Some(delivered) => self.format_amount(delivered),
+                    None if tx.is_partial_payment() => "unknown".to_string(),
+                    None => self.format_amount(amount),
+                };
+                let partial = if tx.is_partial_payment() { " [PARTIAL PAYMENT]" } else { "" };
+                format!("{} -> {}{}{}", 
+                        shown, 
+                        self.format_address(destination), 
+                        tag, 
+                        partial)
+            }
+            TransactionKind::OfferCreate { taker_gets, taker_pays, .. } => {
+                format!("sell {} for {}", self.format_amount(taker_gets), self.format_amount(taker_pays))
+            }
+            TransactionKind::OfferCancel { offer_sequence } => {
+                format!("cancel offer #{}", offer_sequence)
+            }
+            TransactionKind::TrustSet { limit_amount, .. } => {
+                format!("trust limit {}", self.format_amount(limit_amount))
+            }
+            TransactionKind::EscrowCreate { destination, amount, .. } => {
+                format!("escrow {} -> {}", self.format_amount(amount), self.format_address(destination))
+            }
+            TransactionKind::EscrowFinish { owner, offer_sequence } => {
+                format!("finish escrow {}#{}", self.format_address(owner), offer_sequence)
+            }
+            TransactionKind::EscrowCancel { owner, offer_sequence } => {
+                format!("cancel escrow {}#{}", self.format_address(owner), offer_sequence)
+            }
+            TransactionKind::PaymentChannelCreate { destination, amount, settle_delay, .. } => {
+                format!("channel {} -> {} (settle delay {}s)", 
+                        self.format_amount(amount), self.format_address(destination), settle_delay)
+            }
+            TransactionKind::PaymentChannelFund { channel, amount, .. } => {
+                format!("fund channel {} with {}", self.format_hash(channel), self.format_amount(amount))
+            }
+            TransactionKind::PaymentChannelClaim { channel, balance, .. } => {
+                let balance = balance.as_ref().map(|b| format!(", balance {}", self.format_amount(b)));
+                format!("claim channel {}{}", self.format_hash(channel), balance.unwrap_or_default())
+            }
+            TransactionKind::CheckCreate { destination, send_max, .. } => {
+                format!("check up to {} -> {}", self.format_amount(send_max), self.format_address(destination))
+            }
+            TransactionKind::CheckCash { check_id, amount, deliver_min } => {
+                let cashed = amount.as_ref().or(deliver_min.as_ref());
+                let cashed = cashed.map(|a| format!(" for {}", self.format_amount(a)));
+                format!("cash check {}{}", self.format_hash(check_id), cashed.unwrap_or_default())
+            }
+            TransactionKind::CheckCancel { check_id } => {
+                format!("cancel check {}", self.format_hash(check_id))
+            }
+            TransactionKind::NFTokenMint { nftoken_taxon, transfer_fee, .. } => {
+                let fee = transfer_fee.map(|f| format!(", transfer fee {:.3}%", f as f64 / 1000.0));
+                format!("mint NFT (taxon {}{})", nftoken_taxon, fee.unwrap_or_default())
+            }
+            TransactionKind::NFTokenBurn { nftoken_id, .. } => {
+                format!("burn NFT {}", self.format_hash(nftoken_id))
+            }
+            TransactionKind::NFTokenCreateOffer { nftoken_id, amount, .. } => {
+                let side = if tx.flags & TF_SELL_NFTOKEN != 0 { "sell" } else { "buy" };
+                format!("{} offer for NFT {} at {}", side, self.format_hash(nftoken_id), self.format_amount(amount))
+            }
+            TransactionKind::NFTokenCancelOffer { nftoken_offers } => {
+                format!("cancel {} NFT offer(s)", nftoken_offers.len())
+            }
+            TransactionKind::NFTokenAcceptOffer { nftoken_sell_offer, nftoken_buy_offer, .. } => {
+                match (nftoken_sell_offer, nftoken_buy_offer) {
+                    (Some(_), Some(_)) => "broker NFT sale".to_string(),
+                    (Some(offer), None) => format!("accept NFT sell offer {}", self.format_hash(offer)),
+                    (None, Some(offer)) => format!("accept NFT buy offer {}", self.format_hash(offer)),
+                    (None, None) => "accept NFT offer".to_string(),
+                }
+            }
+            TransactionKind::AMMCreate { amount, amount2, trading_fee } => {
+                format!("create AMM {} + {} (fee {:.3}%)", 
+                        self.format_amount(amount), self.format_amount(amount2), *trading_fee as f64 / 1000.0)
+            }
+            TransactionKind::AMMDeposit { asset, asset2, .. } => {
+                format!("deposit to AMM {}/{}", self.format_asset(asset), self.format_asset(asset2))
+            }
+            TransactionKind::AMMWithdraw { asset, asset2, .. } => {
+                format!("withdraw from AMM {}/{}", self.format_asset(asset), self.format_asset(asset2))
+            }
+            TransactionKind::AMMVote { asset, asset2, trading_fee } => {
+                format!("vote fee {:.3}% on AMM {}/{}", 
+                        *trading_fee as f64 / 1000.0, self.format_asset(asset), self.format_asset(asset2))
+            }
+            TransactionKind::AMMBid { asset, asset2, .. } => {
+                format!("bid on AMM {}/{} auction slot", self.format_asset(asset), self.format_asset(asset2))
+            }
+            TransactionKind::AMMDelete { asset, asset2 } => {
+                format!("delete AMM {}/{}", self.format_asset(asset), self.format_asset(asset2))
+            }
+            TransactionKind::AccountSet { set_flag, clear_flag, domain, .. } => {
+                let mut parts = Vec::new();
+                if let Some(flag) = set_flag {
+                    parts.push(format!("set flag {}", flag));
+                }
+                if let Some(flag) = clear_flag {
+                    parts.push(format!("clear flag {}", flag));
+                }
+                if domain.is_some() {
+                    parts.push("update domain".to_string());
+                }
+                if parts.is_empty() {
+                    "account settings".to_string()
+                } else {
+                    parts.join(", ")
+                }
+            }
+            TransactionKind::AccountDelete { destination, .. } => {
+                format!("delete account, remaining XRP -> {}", self.format_address(destination))
+            }
+            TransactionKind::SetRegularKey { regular_key } => match regular_key {
+                Some(key) => format!("regular key -> {}", self.format_address(key)),
+                None => "remove regular key".to_string(),
+            },
+            TransactionKind::SignerListSet { signer_quorum, signer_entries } => {
+                let signers = signer_entries.as_ref().map_or(0, Vec::len);
+                if signers == 0 {
+                    "remove signer list".to_string()
+                } else {
+                    format!("{} signers, quorum {}", signers, signer_quorum)
+                }
+            }
+            TransactionKind::TicketCreate { ticket_count } => {
+                format!("create {} ticket(s)", ticket_count)
+            }
+            TransactionKind::DepositPreauth { authorize, unauthorize } => match (authorize, unauthorize) {
+                (Some(account), _) => format!("preauthorize {}", self.format_address(account)),
+                (None, Some(account)) => format!("revoke preauth {}", self.format_address(account)),
+                (None, None) => "deposit preauth".to_string(),
+            },
+            TransactionKind::Clawback { amount } => {
+                format!("claw back {}", self.format_amount(amount))
+            }
+            TransactionKind::Unknown => String::new(),
+        }
+    }
+    
+    /// Сумма с сокращенным адресом эмитента
+    pub fn format_amount(&self, amount: &Amount) -> String {
+        format!("{} {}", amount.value_string(), self.format_asset(&amount.asset()))
+    }
+    
+    /// Актив (XRP, токен или MPT) с сокращенным адресом эмитента
+    pub fn format_asset(&self, asset: &Asset) -> String {
+        match asset {
+            Asset::Issued { currency, issuer } => format!("{}.{}", currency, self.format_address(issuer)),
+            Asset::Mpt { mpt_issuance_id } => format!("MPT:{}", self.format_hash(mpt_issuance_id)),
+            Asset::Xrp => "XRP".to_string(),
+        }
+    }
+    
+    pub fn display_history_footer(&self, count: usize) {
+        println!("Total: {} transactions", count);
+    }
+    
+    /// Подробности одной транзакции с метаданными (подкоманда tx)
+    pub fn display_transaction(&self, result: &TxResult, details: &DisplayTransactionDetails) {
+        let tx = &details.transaction;
+        
+        println!("Hash: {}", tx.hash);
+        println!("Type: {}", tx.transaction_type);
+        if let Some(result) = &details.result {
+            println!("Result: {}", result);
+        }
+        println!("Validated: {}", if details.validated { "yes" } else { "no" });
+        match &tx.amount {
+            Some(amount) => println!("Amount: {}", amount),
+            None if tx.partial_payment => println!("Amount: unknown"),
+            None => {}
+        }
+        // У частичного платежа Amount - лишь верхняя граница доставленной суммы
+        if tx.partial_payment {
+            if let Some(declared) = result.tx.amount() {
+                println!("Partial Payment: yes (declared amount {})", declared);
+            }
+        }
+        println!("Details: {}", self.format_transaction_summary(&result.tx, result.meta.as_ref()));
+        println!("From: {}", tx.from);
+        if let Some(to) = &tx.to {
+            println!("To: {}", to);
+        }
+        if let Some(fee) = tx.fee {
+            println!("Fee: {} XRP", fee);
+        }
+        if let Some(sequence) = tx.sequence {
+            println!("Sequence: {}", sequence);
+        }
+        if let Some(ledger_index) = tx.ledger_index {
+            println!("Ledger: {}", ledger_index);
+        }
+        println!("Timestamp: {}", self.format_datetime(tx.timestamp.as_ref()));
+        
+        if !details.memos.is_empty() {
+            println!("Memos:");
+            for memo in &details.memos {
+                let parts: Vec<String> = [("type", &memo.memo_type), ("format", &memo.memo_format), ("data", &memo.memo_data)]
+                    .into_iter()
+                    .filter_map(|(name, value)| value.as_ref().map(|value| format!("{}: {}", name, value)))
+                    .collect();
+                println!(" - {}", parts.join(", "));
+            }
+        }
+        
+        if !details.affected_nodes.is_empty() {
+            println!("Affected Nodes:");
+            for node in &details.affected_nodes {
+                self.display_affected_node(node);
+            }
+        }
+    }
+    
+    /// Объект леджера и изменения его полей
+    fn display_affected_node(&self, node: &DisplayAffectedNode) {
+        println!(" {:<9} {:<16} {}", node.change, node.ledger_entry_type, self.format_hash(&node.ledger_index));
+        
+        let Some(fields) = &node.fields else {
+            return;
+        };
+        if let Some(account) = fields["Account"].as_str() {
+            println!("     Account: {}", account);
+        }
+        if let Some(Value::Object(previous)) = &node.previous_fields {
+            for (name, old_value) in previous {
+                println!("     {}: {} -> {}", name, self.format_field(name, old_value), self.format_field(name, &fields[name]));
+            }
+        }
+    }
+    
+    /// Значение поля объекта леджера: суммы - в читаемом виде, прочее - как есть
+    fn format_field(&self, name: &str, value: &Value) -> String {
+        const AMOUNT_FIELDS: [&str; 6] = ["Amount", "Balance", "HighLimit", "LowLimit", "TakerGets", "TakerPays"];
+        
+        if AMOUNT_FIELDS.contains(&name) {
+            if let Ok(amount) = Amount::deserialize(value) {
+                return self.format_amount(&amount);
+            }
+        }
+        match value {
+            Value::String(text) => text.clone(),
+            Value::Null => "-".to_string(),
+            other => other.to_string(),
+        }
+    }
+    
+    /// Результат проверки адреса (подкоманда validate)
+    pub fn display_address(&self, address: &DisplayAddress) {
+        self.display_validation_info(&address.input, address.valid);
+        
+        if let Some(classic) = &address.classic_address {
+            println!("Classic Address: {}", classic);
+        }
+        if let Some(x_address) = &address.x_address {
+            println!("X-Address: {}", x_address);
+        }
+        if let Some(account_id) = &address.account_id {
+            println!("Account ID: {}", account_id);
+        }
+        if let Some(tag) = address.tag {
+            println!("Destination Tag: {}", tag);
+        }
+        if let Some(network) = &address.network {
+            println!("Network: {}", network);
+        }
+    }
+    
+    pub fn display_trust_lines(&self, address: &AddressInfo, lines: &[TrustLine]) {
+        println!("Trust Lines: {}", address.classic_address());
+        for line in lines {
+            println!(" {:<10} {:>24} / limit {:<16} {}", 
+                     line.currency.display_name(), 
+                     line.balance, 
+                     line.limit, 
+                     self.format_address(&line.account));
+            
+            let mut details = Vec::new();
+            if line.has_peer_limit() {
+                details.push(format!("peer limit {}", line.limit_peer));
+            }
+            if line.quality_in != 0 {
+                details.push(format!("quality in {}", format_quality(line.quality_in)));
+            }
+            if line.quality_out != 0 {
+                details.push(format!("quality out {}", format_quality(line.quality_out)));
+            }
+            details.extend(line.flag_names().into_iter().map(str::to_string));
+            if !details.is_empty() {
+                println!("            {}", details.join(", "));
+            }
+        }
+        println!("Total: {} trust lines", lines.len());
+    }
+    
+    pub fn display_offers(&self, address: &AddressInfo, offers: &[AccountOffer]) {
+        println!("Offers: {}", address.classic_address());
+        for offer in offers {
+            println!(" #{:<10} sell {} for {}", 
+                     offer.seq, 
+                     self.format_amount(&offer.taker_gets), 
+                     self.format_amount(&offer.taker_pays));
+            
+            let mut details = Vec::new();
+            if let Some(price) = offer.price() {
+                details.push(format!("price {} {} per {}", 
+                                     format_price(price), 
+                                     offer.taker_pays.asset().code(), 
+                                     offer.taker_gets.asset().code()));
+            }
+            if offer.expiration.is_some() {
+                details.push(format!("expires {}", self.format_datetime(offer.expiration().as_ref())));
+            }
+            if offer.is_passive() {
+                details.push("passive".to_string());
+            }
+            if offer.is_sell() {
+                details.push("sell".to_string());
+            }
+            if !details.is_empty() {
+                println!("             {}", details.join(", "));
+            }
+        }
+        println!("Total: {} offers", offers.len());
+    }
+    
+    /// Книга заявок: asks от дальних к лучшей, спред, затем bids от лучшей к дальним
+    pub fn display_order_book(&self, base: &Asset, quote: &Asset, levels: &[DisplayBookLevel]) {
+        println!("Order Book: {} / {}", self.format_asset(base), self.format_asset(quote));
+        println!(" {:<4} {:>18} {:>18} {:>18}  Account", "Side", "Price", "Size", "Cumulative");
+        
+        let asks: Vec<&DisplayBookLevel> = levels.iter().filter(|level| level.side == BookSide::Ask).collect();
+        let bids: Vec<&DisplayBookLevel> = levels.iter().filter(|level| level.side == BookSide::Bid).collect();
+        let print_level = |level: &DisplayBookLevel| {
+            println!(" {:<4} {:>18} {:>18} {:>18}  {}", 
+                     if level.side == BookSide::Ask { "ask" } else { "bid" }, 
+                     format_price(level.price), 
+                     format_price(level.size), 
+                     format_price(level.cumulative_size), 
+                     self.format_address(&level.account));
+        };
+        
+        for level in asks.iter().rev() {
+            print_level(level);
+        }
+        println!(" {}", "-".repeat(60));
+        for level in &bids {
+            print_level(level);
+        }
+        
+        let summary = BookSummary::from_levels(levels);
+        let format_best = |price: Option<f64>| {
+            price.map(|price| format!("{} {}", format_price(price), quote.code())).unwrap_or_else(|| "-".to_string())
+        };
+        println!("Best Ask: {}", format_best(summary.best_ask));
+        println!("Best Bid: {}", format_best(summary.best_bid));
+        if let (Some(spread), Some(percent)) = (summary.spread, summary.spread_percent()) {
+            println!("Spread: {} {} ({:.2}% of mid)", format_price(spread), quote.code(), percent);
+        }
+        println!("Total: {} asks, {} bids", asks.len(), bids.len());
+    }
+    
+    pub fn display_nfts(&self, address: &AddressInfo, nfts: &[NfToken]) {
+        println!("NFTs: {}", address.classic_address());
+        for nft in nfts {
+            let uri = nft.uri.as_deref().map(|uri| codec::decode_hex_text(uri).unwrap_or_else(|| uri.to_string()));
+            println!(" {}  taxon {:<6} serial {:<8} issuer {}  {}", 
+                     nft.nftoken_id, 
+                     nft.nftoken_taxon, 
+                     nft.nft_serial, 
+                     self.format_address(&nft.issuer), 
+                     uri.unwrap_or_default());
+        }
+        println!("Total: {} NFTs", nfts.len());
+    }
+    
+    pub fn display_objects(&self, address: &AddressInfo, objects: &[DisplayLedgerObject]) {
+        println!("Objects: {}", address.classic_address());
+        for object in objects {
+            println!(" {:<20} {}", object.ledger_entry_type, object.index);
+        }
+        println!("Total: {} objects", objects.len());
+    }
+    
+    pub fn display_ledger(&self, ledger: &DisplayLedger) {
+        match ledger.ledger_index {
+            Some(index) => println!("Ledger: {}", index),
+            None => println!("Ledger: Нет данных"),
+        }
+        if let Some(hash) = &ledger.ledger_hash {
+            println!("Hash: {}", hash);
+        }
+        println!("Parent Hash: {}", ledger.parent_hash);
+        println!("Close Time: {}", self.format_datetime(ledger.close_time.as_ref()));
+        println!("Total Coins: {} XRP", ledger.total_coins);
+        println!("Closed: {}", if ledger.closed { "yes" } else { "no" });
+        println!("Validated: {}", if ledger.validated { "yes" } else { "no" });
+    }
+    
+    pub fn display_server_info(&self, info: &ServerInfo) {
+        println!("Version: {}", info.build_version);
+        println!("State: {}", info.server_state);
+        if let Some(network_id) = info.network_id {
+            println!("Network ID: {}", network_id);
+        }
+        println!("Complete Ledgers: {}", info.complete_ledgers);
+        if let Some(peers) = info.peers {
+            println!("Peers: {}", peers);
+        }
+        if let Some(uptime) = info.uptime {
+            println!("Uptime: {}s", uptime);
+        }
+        if let Some(load_factor) = info.load_factor {
+            println!("Load Factor: {}", load_factor);
+        }
+        match &info.validated_ledger {
+            Some(ledger) => {
+                println!("Validated Ledger:");
+                println!(" Index: {}", ledger.seq);
+                println!(" Hash: {}", ledger.hash);
+                println!(" Age: {}s", ledger.age);
+                println!(" Base Fee: {} XRP", ledger.base_fee_xrp);
+                println!(" Base Reserve: {} XRP", ledger.reserve_base_xrp);
+                println!(" Owner Reserve: {} XRP", ledger.reserve_inc_xrp);
+            }
+            None => println!("Validated Ledger: Нет данных"),
+        }
+    }
+    
+    pub fn display_watch_header(&self, addresses: &[AddressInfo]) {
+        let addresses: Vec<String> = addresses.iter().map(AddressInfo::classic_address).collect();
+        println!("Watching: {}", addresses.join(", "));
+    }
+    
+    /// Транзакция наблюдаемого адреса: строка истории, код результата при неудаче
+    /// и изменение баланса XRP адреса
+    pub fn display_watch_entry(&self, address: &AddressInfo, wrapper: &TransactionWrapper) {
+        let classic_address = address.classic_address();
+        print!("[{}]", self.format_address(&classic_address));
+        self.display_history_entry(wrapper);
+        
+        let Some(meta) = &wrapper.meta else {
+            return;
+        };
+        if meta.transaction_result != "tesSUCCESS" {
+            println!("    Result: {}", meta.transaction_result);
+        }
+        if let Some(change) = meta.xrp_balance_change(&classic_address) {
+            let sign = if change.delta < 0 { "-" } else { "+" };
+            println!("    Balance: {}{} XRP -> {} XRP", sign, Drops(change.delta.unsigned_abs()), change.balance);
+        }
+    }
+    
+    fn format_datetime(&self, datetime: Option<&DateTime<Utc>>) -> String {
+        match datetime {
+            Some(datetime) => self.time.format(datetime),
+            None => "Нет данных".to_string(),
+        }
+    }
+    
     fn format_address(&self, address: &str) -> String {
         if address.len() <= 12 {
             address.to_string()
@@ -55,15 +634,9 @@ impl DisplayFormatter {
             format!("{}...{}", &hash[..6], &hash[hash.len()-6..])
         }
     }
-    #[allow(dead_code)]
-    pub fn display_balance_only(&self, address: &str, account_data: &AccountData) {
-        let display_info = DisplayAccountInfo::from_account_data(
-            address.to_string(), 
-            account_data
-        );
-        
-        println!("Address: {}", self.format_address(address));
-        println!("Balance: {:.6} XRP", display_info.balance_xrp);
+    pub fn display_balance_only(&self, account_data: &AccountData) {
+        println!("Address: {}", self.format_address(&account_data.account));
+        println!("Balance: {} XRP", account_data.balance);
     }
     
     #[allow(dead_code)]
@@ -71,7 +644,6 @@ impl DisplayFormatter {
         eprintln!("Ошибка: {}", error_message);
     }
     
-    #[allow(dead_code)]
     pub fn display_validation_info(&self, address: &str, is_valid: bool) {
         if is_valid {
             println!("Адрес {} является валидным XRP-адресом", address);
@@ -87,6 +659,24 @@ impl Default for DisplayFormatter {
     }
 }
 
+/// Цена с 10 значащими цифрами без лишних нулей
+fn format_price(price: f64) -> String {
+    let magnitude = if price == 0.0 { 0 } else { price.abs().log10().floor() as i32 };
+    let decimals = (9 - magnitude).clamp(0, 20) as usize;
+    let text = format!("{:.*}", decimals, price);
+    if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        text
+    }
+}
+
+/// Курс в миллиардных долях (QualityIn/Out, TransferRate): 1000000000 -> 1
+fn format_quality(quality: u32) -> String {
+    let text = format!("{}.{:09}", quality / 1_000_000_000, quality % 1_000_000_000);
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
 #[allow(dead_code)]
 pub fn print_separator() {
     println!("{}", "-".repeat(50));
@@ -0,0 +1,233 @@
+// error.rs - ошибки верхнего уровня и коды завершения процесса
+use std::time::Duration;
+
+use thiserror::Error;
+
+use crate::address::AddressError;
+use crate::amount::AmountError;
+use crate::models::ErrorResult;
+use crate::network::NetworkError;
+use crate::retry::RetryClass;
+use crate::rippled_error::RippledError;
+
+/// Коды завершения процесса
+pub mod exit_code {
+    /// Прочие ошибки (например, при выводе результата)
+    pub const INTERNAL: u8 = 1;
+    /// Некорректные входные данные: адрес, сеть, аргументы (clap тоже завершается с кодом 2)
+    pub const VALIDATION: u8 = 2;
+    /// Кошелек, транзакция или леджер не найдены
+    pub const NOT_FOUND: u8 = 3;
+    /// Сервер недоступен: ошибка соединения, таймаут, HTTP-ошибка
+    pub const NETWORK: u8 = 4;
+    /// Сервер вернул ошибку JSON-RPC
+    pub const RPC: u8 = 5;
+    /// Ответ сервера не удалось разобрать
+    pub const PARSE: u8 = 6;
+}
+
+/// Описание кодов завершения для `--help`
+pub const EXIT_CODES_HELP: &str = "\
+Коды завершения:
+  0  успех
+  1  прочие ошибки
+  2  некорректные входные данные (адрес, сеть, аргументы)
+  3  кошелек, транзакция или леджер не найдены
+  4  сервер недоступен (соединение, таймаут, HTTP-ошибка)
+  5  сервер вернул ошибку JSON-RPC
+  6  ответ сервера не удалось разобрать";
+
+/// Ошибки выполнения команд
+#[derive(Error, Debug)]
+pub enum ViewerError {
+    /// Некорректные входные данные
+    #[error("{0}")]
+    Validation(String),
+
+    /// Запрошенный объект отсутствует в леджере
+    #[error("{0}")]
+    NotFound(String),
+
+    /// Ошибка соединения или таймаут
+    #[error("Не удается подключиться к API ({0})")]
+    Network(String),
+
+    /// Сервер ответил кодом HTTP, отличным от 2xx
+    #[error("Сервер ответил HTTP {status}")]
+    Http {
+        status: u16,
+        /// Значение заголовка Retry-After
+        retry_after: Option<Duration>,
+    },
+
+    /// Ошибка, возвращенная сервером в ответе JSON-RPC
+    #[error("Сервер вернул ошибку {error}: {message}")]
+    Rpc {
+        error: RippledError,
+        code: Option<i32>,
+        message: String,
+    },
+
+    /// Ответ сервера не соответствует ожидаемому формату
+    #[error("Не удалось разобрать ответ API ({0})")]
+    Parse(String),
+
+    /// Ошибка записи результата (например, закрытый stdout)
+    #[error("Ошибка вывода: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Ошибка формирования CSV
+    #[error("Ошибка вывода CSV: {0}")]
+    Csv(#[from] csv::Error),
+
+    /// Ошибка формирования YAML
+    #[error("Ошибка вывода YAML: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+
+    /// Ошибка сериализации JSON при выводе.
+    /// Без `#[from]`: ошибки разбора JSON от сервера относятся к `Parse`
+    #[error("Ошибка вывода JSON: {0}")]
+    Json(serde_json::Error),
+}
+
+impl ViewerError {
+    /// Код завершения процесса для ошибки
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            ViewerError::Validation(_) => exit_code::VALIDATION,
+            ViewerError::NotFound(_) => exit_code::NOT_FOUND,
+            ViewerError::Network(_) | ViewerError::Http { .. } => exit_code::NETWORK,
+            ViewerError::Rpc { .. } => exit_code::RPC,
+            ViewerError::Parse(_) => exit_code::PARSE,
+            ViewerError::Io(_) | ViewerError::Csv(_) | ViewerError::Yaml(_) | ViewerError::Json(_) => {
+                exit_code::INTERNAL
+            }
+        }
+    }
+
+    /// Класс временного сбоя; None - повтор запроса не поможет
+    pub fn retry_class(&self) -> Option<RetryClass> {
+        match self {
+            ViewerError::Network(_) => Some(RetryClass::Network),
+            ViewerError::Http { status: 429, .. } => Some(RetryClass::RateLimited),
+            ViewerError::Http { status: 503, .. } => Some(RetryClass::ServerBusy),
+            ViewerError::Http { status, .. } if *status >= 500 => Some(RetryClass::Network),
+            ViewerError::Rpc { error, .. } => match error {
+                RippledError::SlowDown => Some(RetryClass::RateLimited),
+                RippledError::TooBusy
+                | RippledError::NoNetwork
+                | RippledError::NoCurrent
+                | RippledError::NoClosed
+                | RippledError::NotSynced => Some(RetryClass::ServerBusy),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Задержка, запрошенная сервером (заголовок Retry-After)
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            ViewerError::Http { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+}
+
+impl From<AddressError> for ViewerError {
+    fn from(error: AddressError) -> Self {
+        let message = match error {
+            AddressError::TooShort => "Адрес слишком короткий".to_string(),
+            AddressError::TooLong => "Адрес слишком длинный".to_string(),
+            AddressError::InvalidPrefix => "Адрес должен начинаться с 'r', 'X' или 'T'".to_string(),
+            AddressError::InvalidChecksum => "Неверная контрольная сумма адреса".to_string(),
+            AddressError::InvalidBase58 | AddressError::InvalidDataLength | AddressError::InvalidVersion => {
+                "Некорректный формат адреса".to_string()
+            }
+            AddressError::InvalidXAddress(e) => format!("Некорректный X-адрес ({})", e),
+            AddressError::SecretSeed => {
+                "Указан секретный seed, а не адрес; никому не передавайте его".to_string()
+            }
+            AddressError::PublicKey => "Указан публичный ключ, а не адрес".to_string(),
+        };
+        ViewerError::Validation(message)
+    }
+}
+
+impl From<NetworkError> for ViewerError {
+    fn from(error: NetworkError) -> Self {
+        ViewerError::Validation(error.to_string())
+    }
+}
+
+impl From<reqwest::Error> for ViewerError {
+    fn from(error: reqwest::Error) -> Self {
+        // reqwest прячет причину (DNS, TLS, отказ в соединении) в цепочке source
+        let mut message = error.to_string();
+        let mut source = std::error::Error::source(&error);
+        while let Some(cause) = source {
+            message.push_str(": ");
+            message.push_str(&cause.to_string());
+            source = cause.source();
+        }
+        ViewerError::Network(message)
+    }
+}
+
+impl From<ErrorResult> for ViewerError {
+    fn from(result: ErrorResult) -> Self {
+        ViewerError::Rpc {
+            message: result.error_message.unwrap_or_else(|| "описание отсутствует".to_string()),
+            error: result.error,
+            code: result.error_code,
+        }
+    }
+}
+
+/// Суммы приходят от сервера, поэтому ошибка в них - ошибка ответа
+impl From<AmountError> for ViewerError {
+    fn from(error: AmountError) -> Self {
+        ViewerError::Parse(error.to_string())
+    }
+}
+
+impl From<serde_json::Error> for ViewerError {
+    fn from(error: serde_json::Error) -> Self {
+        ViewerError::Parse(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn server_json_errors_are_parse_errors() {
+        let error: ViewerError = serde_json::from_str::<u32>("{").unwrap_err().into();
+        assert!(matches!(error, ViewerError::Parse(_)));
+        assert_eq!(error.exit_code(), exit_code::PARSE);
+    }
+
+    #[test]
+    fn output_errors_are_internal() {
+        let io: ViewerError = std::io::Error::from(std::io::ErrorKind::BrokenPipe).into();
+        assert!(matches!(io, ViewerError::Io(_)));
+        assert_eq!(io.exit_code(), exit_code::INTERNAL);
+
+        let json = ViewerError::Json(serde_json::from_str::<u32>("{").unwrap_err());
+        assert_eq!(json.exit_code(), exit_code::INTERNAL);
+    }
+
+    fn rpc(error: RippledError) -> ViewerError {
+        ViewerError::Rpc { error, code: None, message: String::new() }
+    }
+
+    #[test]
+    fn overloaded_server_errors_are_retried() {
+        assert_eq!(rpc(RippledError::SlowDown).retry_class(), Some(RetryClass::RateLimited));
+        assert_eq!(rpc(RippledError::TooBusy).retry_class(), Some(RetryClass::ServerBusy));
+        assert_eq!(rpc(RippledError::NotSynced).retry_class(), Some(RetryClass::ServerBusy));
+        assert_eq!(rpc(RippledError::ActNotFound).retry_class(), None);
+        assert_eq!(rpc(RippledError::Other("slowdown".to_string())).retry_class(), None);
+    }
+}
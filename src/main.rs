@@ -1,109 +1,197 @@
 // main.rs - точка входа в программу
-use clap::Parser;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use clap::{ArgAction, Parser, Subcommand};
 
 // Подключаем наши модули
 mod address;
+mod amount;
 mod api;
+mod codec;
+mod commands;
 mod display;
+mod error;
+mod meta;
 mod models;
+mod network;
+mod output;
+mod pool;
+mod retry;
+mod rippled_error;
+#[cfg(test)]
+mod test_fixtures;
+mod time;
+mod transaction;
+mod transport;
+mod xaddress;
 
 // Подключаем конкретные элементы из модулей
-use address::{AddressValidator, AddressError};
-use api::XrpApi;
+use commands::{
+    balance, book, history, info, ledger, lines, nfts, objects, offers, server, tx, validate, watch,
+    AddressArgs, AppContext, CommandResult,
+};
 use display::DisplayFormatter;
+use network::{Network, NetworkConfig};
+use output::OutputFormat;
+use retry::{RetryClass, RetryPolicy};
+use time::{TimeFormatter, TimeStyle, TimeZoneMode};
 
-/// CLI-приложение для просмотра баланса XRP-кошелька
+/// CLI-приложение для просмотра XRP-кошельков и состояния XRPL
 #[derive(Debug, Parser)]
-#[clap(name = "xrp-viewer", version = "0.1.0", about = "Просмотр баланса XRP-кошелька")]
+#[clap(name = "xrp-viewer", version = "0.1.0", about = "Просмотр XRP-кошельков и состояния XRPL")]
+#[clap(subcommand_negates_reqs = true, after_help = error::EXIT_CODES_HELP)]
 struct Cli {
-    /// Публичный XRP-адрес (начинается с 'r')
-    #[clap(value_parser)]
-    address: String,
+    #[clap(subcommand)]
+    command: Option<Command>,
+    
+    /// Адрес без подкоманды - то же, что `info <адрес>`
+    #[clap(value_parser, required = true)]
+    address: Option<String>,
+    
+    /// Сеть XRPL (custom требует --url)
+    #[clap(long, value_enum, global = true)]
+    network: Option<Network>,
+    
+    /// URL серверов rippled (http(s):// - JSON-RPC, ws(s):// - WebSocket) через запятую или повтором флага
+    /// (переопределяют серверы сети; запрос уходит на наиболее синхронизированный)
+    #[clap(long = "url", env = "XRP_VIEWER_URL", value_delimiter = ',', global = true)]
+    urls: Vec<String>,
+    
+    /// Формат вывода результатов
+    #[clap(long, short, value_enum, default_value_t = OutputFormat::Text, global = true)]
+    output: OutputFormat,
+    
+    /// Часовой пояс для вывода дат
+    #[clap(long, value_enum, default_value_t = TimeZoneMode::Utc, global = true)]
+    tz: TimeZoneMode,
+    
+    /// Формат вывода дат
+    #[clap(long, value_enum, default_value_t = TimeStyle::Default, global = true)]
+    time_format: TimeStyle,
+    
+    /// Максимальное число попыток запроса к серверу (1 - без повторов)
+    #[clap(long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..), global = true)]
+    max_attempts: u32,
+    
+    /// Задержка перед первым повтором, мс (далее растет экспоненциально)
+    #[clap(long, default_value_t = 500, global = true)]
+    retry_delay_ms: u64,
+    
+    /// Классы сбоев, после которых запрос повторяется
+    #[clap(long, value_enum, value_delimiter = ',', default_values_t = [RetryClass::Network, RetryClass::RateLimited, RetryClass::ServerBusy], global = true)]
+    retry_on: Vec<RetryClass>,
+    
+    /// Подробный лог: -v (info), -vv (debug), -vvv (trace); без флага действует RUST_LOG (по умолчанию warn)
+    #[clap(long, short, action = ArgAction::Count, global = true)]
+    verbose: u8,
+    
+    /// Отключить лог полностью
+    #[clap(long, short, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Баланс кошелька
+    Balance(AddressArgs),
+    /// Сводка по кошельку: баланс, токены и последняя транзакция
+    Info(AddressArgs),
+    /// История транзакций кошелька (с постраничной загрузкой)
+    History(history::HistoryArgs),
+    /// Транзакция по хешу
+    Tx(tx::TxArgs),
+    /// Проверка адреса без обращения к сети
+    Validate(AddressArgs),
+    /// Линии доверия (токены) кошелька
+    Lines(AddressArgs),
+    /// Открытые предложения кошелька на DEX
+    Offers(AddressArgs),
+    /// Книга заявок DEX по паре активов: глубина, лучшие цены и спред
+    Book(book::BookArgs),
+    /// NFT кошелька
+    Nfts(AddressArgs),
+    /// Объекты леджера, принадлежащие кошельку
+    Objects(objects::ObjectsArgs),
+    /// Заголовок леджера
+    Ledger(ledger::LedgerArgs),
+    /// Состояние сервера rippled
+    Server,
+    /// Наблюдение за новыми транзакциями кошельков
+    Watch(watch::WatchArgs),
 }
 
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Инициализация логирования
-    env_logger::init();
-    
+async fn main() -> ExitCode {
     // Парсинг аргументов командной строки
     let cli = Cli::parse();
     
-    log::debug!("Получен адрес: {}", cli.address);
-    
-    // Создаем валидатор адреса
-    let validator = AddressValidator::new();
-    
-    // Валидация адреса с подробным логированием
-    log::debug!("Начинаем валидацию адреса...");
-    match validator.validate(&cli.address) {
-        Ok(()) => {
-            log::info!("Адрес {} прошел валидацию", cli.address);
-            
-            // Создаем клиент API
-            let api_client = XrpApi::new()?;
-            
-            // Получаем информацию о кошельке
-            match api_client.get_account_info(&cli.address).await {
-                Ok(account_info) => {
-                    log::info!("Получена информация о кошельке");
-                    
-                    // Получаем последние транзакции
-                    match api_client.get_latest_transaction(&cli.address).await {
-                        Ok(transaction) => {
-                            // Создаем форматировщик вывода
-                            let formatter = DisplayFormatter::new();
-                            
-                            // Выводим информацию
-                            formatter.display_account_info(
-                                &cli.address, 
-                                &account_info.result.account_data, 
-                                transaction.as_ref()
-                            );
-                            
-                            Ok(())
-                        }
-                        Err(e) => {
-                            log::error!("Ошибка получения транзакций: {}", e);
-                            eprintln!("Ошибка: Не удается подключиться к API");
-                            Ok(())
-                        }
-                    }
-                }
-                Err(e) => {
-                    log::error!("Ошибка API: {}", e);
-                    if e.to_string().contains("actNotFound") || e.to_string().contains("Account not found") {
-                        eprintln!("Ошибка: Кошелек не найден");
-                    } else {
-                        eprintln!("Ошибка: Не удается подключиться к API");
-                    }
-                    Ok(())
-                }
-            }
+    // Инициализация логирования
+    init_logging(cli.verbose, cli.quiet);
+    
+    match run(cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            log::debug!("Завершение с ошибкой: {:?}", e);
+            eprintln!("Ошибка: {}", e);
+            ExitCode::from(e.exit_code())
         }
-        Err(error) => {
-            log::error!("Ошибка валидации адреса {}: {:?}", cli.address, error);
-            match error {
-                AddressError::TooShort => {
-                    eprintln!("Ошибка: Адрес слишком короткий");
-                }
-                AddressError::TooLong => {
-                    eprintln!("Ошибка: Адрес слишком длинный");
-                }
-                AddressError::InvalidPrefix => {
-                    eprintln!("Ошибка: Адрес должен начинаться с 'r'");
-                }
-                AddressError::InvalidBase58 => {
-                    eprintln!("Ошибка: Некорректный формат адреса");
-                }
-                AddressError::InvalidChecksum => {
-                    eprintln!("Ошибка: Некорректный формат адреса");
-                }
-                AddressError::InvalidDataLength => {
-                    eprintln!("Ошибка: Некорректный формат адреса");
-                }
-            }
-            Ok(())
+    }
+}
+
+async fn run(cli: Cli) -> CommandResult {
+    // Определяем сеть и сервер
+    let network = NetworkConfig::resolve(cli.network, cli.urls.clone())?;
+    log::debug!("Сеть: {}, серверы: {}", network.network, network.urls.join(", "));
+    
+    let ctx = AppContext {
+        network,
+        formatter: DisplayFormatter::with_time_formatter(TimeFormatter::new(cli.tz, cli.time_format)),
+        output: cli.output,
+        retry: RetryPolicy {
+            max_attempts: cli.max_attempts,
+            base_delay: Duration::from_millis(cli.retry_delay_ms),
+            retry_on: cli.retry_on,
+            ..RetryPolicy::default()
+        },
+    };
+    
+    match cli.command {
+        Some(Command::Balance(args)) => balance::run(&ctx, args).await,
+        Some(Command::Info(args)) => info::run(&ctx, args).await,
+        Some(Command::History(args)) => history::run(&ctx, args).await,
+        Some(Command::Tx(args)) => tx::run(&ctx, args).await,
+        Some(Command::Validate(args)) => validate::run(&ctx, args).await,
+        Some(Command::Lines(args)) => lines::run(&ctx, args).await,
+        Some(Command::Offers(args)) => offers::run(&ctx, args).await,
+        Some(Command::Book(args)) => book::run(&ctx, args).await,
+        Some(Command::Nfts(args)) => nfts::run(&ctx, args).await,
+        Some(Command::Objects(args)) => objects::run(&ctx, args).await,
+        Some(Command::Ledger(args)) => ledger::run(&ctx, args).await,
+        Some(Command::Server) => server::run(&ctx).await,
+        Some(Command::Watch(args)) => watch::run(&ctx, args).await,
+        None => {
+            let address = cli.address.expect("clap требует адрес без подкоманды");
+            info::run(&ctx, AddressArgs { address }).await
         }
     }
-}
\ No newline at end of file
+}
+
+/// Уровень лога из флагов -v/-q; без флагов - из RUST_LOG, по умолчанию warn
+/// (предупреждения о повторах запросов видны без дополнительных флагов)
+fn init_logging(verbose: u8, quiet: bool) {
+    let mut builder = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn"));
+    
+    let level = match (quiet, verbose) {
+        (true, _) => Some(log::LevelFilter::Off),
+        (false, 0) => None,
+        (false, 1) => Some(log::LevelFilter::Info),
+        (false, 2) => Some(log::LevelFilter::Debug),
+        (false, _) => Some(log::LevelFilter::Trace),
+    };
+    if let Some(level) = level {
+        builder.filter_level(level);
+    }
+    
+    builder.init();
+}
@@ -0,0 +1,200 @@
+// meta.rs - метаданные транзакции: результат и затронутые объекты леджера
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::amount::{Amount, Drops};
+
+/// Метаданные выполненной транзакции (поле `meta`)
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct TransactionMeta {
+    /// Код результата, например tesSUCCESS
+    pub transaction_result: String,
+    #[serde(default)]
+    pub affected_nodes: Vec<AffectedNode>,
+    /// Доставленная сумма, вычисленная сервером ("unavailable" у транзакций до 2014 года)
+    #[serde(rename = "delivered_amount")]
+    delivered_amount: Option<Value>,
+    /// Доставленная сумма из самих метаданных (частичные платежи после 2014 года)
+    #[serde(rename = "DeliveredAmount")]
+    delivered_amount_field: Option<Amount>,
+}
+
+/// Объект леджера, созданный, измененный или удаленный транзакцией
+#[derive(Deserialize, Debug, Clone)]
+pub enum AffectedNode {
+    #[serde(rename = "CreatedNode")]
+    Created(LedgerNode),
+    #[serde(rename = "ModifiedNode")]
+    Modified(LedgerNode),
+    #[serde(rename = "DeletedNode")]
+    Deleted(LedgerNode),
+}
+
+/// Поля объекта леджера до и после транзакции
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct LedgerNode {
+    pub ledger_entry_type: String,
+    pub ledger_index: String,
+    /// Поля созданного объекта
+    pub new_fields: Option<Value>,
+    /// Поля после транзакции (у удаленного - последнее состояние)
+    pub final_fields: Option<Value>,
+    /// Прежние значения только тех полей, что изменились
+    pub previous_fields: Option<Value>,
+}
+
+/// Изменение баланса XRP счета
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BalanceChange {
+    /// Изменение в drops (отрицательное - списание)
+    pub delta: i64,
+    /// Баланс после транзакции
+    pub balance: Drops,
+}
+
+impl TransactionMeta {
+    /// Фактически доставленная сумма платежа или чека; None, если неизвестна
+    pub fn delivered_amount(&self) -> Option<Amount> {
+        self.delivered_amount
+            .as_ref()
+            .and_then(|amount| Amount::deserialize(amount).ok())
+            .or_else(|| self.delivered_amount_field.clone())
+    }
+
+    /// Изменение баланса XRP счета `account`; None, если баланс не менялся
+    pub fn xrp_balance_change(&self, account: &str) -> Option<BalanceChange> {
+        self.affected_nodes.iter().find_map(|node| node.xrp_balance_change(account))
+    }
+
+    /// Упоминается ли счет в полях затронутых объектов (владелец, эмитент линии доверия и т.п.)
+    pub fn mentions(&self, account: &str) -> bool {
+        self.affected_nodes.iter().any(|node| {
+            let node = node.node();
+            [&node.new_fields, &node.final_fields]
+                .into_iter()
+                .flatten()
+                .any(|fields| contains_string(fields, account))
+        })
+    }
+}
+
+impl AffectedNode {
+    /// Вид изменения: created, modified или deleted
+    pub fn change(&self) -> &'static str {
+        match self {
+            AffectedNode::Created(_) => "created",
+            AffectedNode::Modified(_) => "modified",
+            AffectedNode::Deleted(_) => "deleted",
+        }
+    }
+
+    pub fn node(&self) -> &LedgerNode {
+        match self {
+            AffectedNode::Created(node) | AffectedNode::Modified(node) | AffectedNode::Deleted(node) => node,
+        }
+    }
+
+    fn xrp_balance_change(&self, account: &str) -> Option<BalanceChange> {
+        let (node, fields, previous) = match self {
+            // Новый счет: баланс вырос с нуля
+            AffectedNode::Created(node) => (node, node.new_fields.as_ref()?, Some(Drops::ZERO)),
+            // Без Balance в PreviousFields баланс не изменился
+            AffectedNode::Modified(node) | AffectedNode::Deleted(node) => (
+                node,
+                node.final_fields.as_ref()?,
+                node.previous_fields.as_ref().and_then(balance_field),
+            ),
+        };
+
+        if node.ledger_entry_type != "AccountRoot" || fields["Account"] != account {
+            return None;
+        }
+
+        let balance = balance_field(fields)?;
+        let previous = previous?;
+        Some(BalanceChange {
+            delta: balance.0 as i64 - previous.0 as i64,
+            balance,
+        })
+    }
+}
+
+/// Ищет строку среди значений полей, включая вложенные объекты (HighLimit, LowLimit)
+fn contains_string(value: &Value, needle: &str) -> bool {
+    match value {
+        Value::String(s) => s == needle,
+        Value::Object(fields) => fields.values().any(|v| contains_string(v, needle)),
+        Value::Array(items) => items.iter().any(|v| contains_string(v, needle)),
+        _ => false,
+    }
+}
+
+fn balance_field(fields: &Value) -> Option<Drops> {
+    fields["Balance"].as_str()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{self, account_root, ACCOUNT, OTHER};
+    use serde_json::json;
+
+    fn meta(nodes: Value) -> TransactionMeta {
+        serde_json::from_value(test_fixtures::meta(nodes)).unwrap()
+    }
+
+    #[test]
+    fn computes_debit_and_credit() {
+        let meta = meta(json!([
+            account_root(ACCOUNT, Some("100000000"), "98999988"),
+            account_root(OTHER, Some("50000000"), "51000000"),
+        ]));
+        assert_eq!(
+            meta.xrp_balance_change(ACCOUNT),
+            Some(BalanceChange { delta: -1_000_012, balance: Drops(98_999_988) })
+        );
+        assert_eq!(
+            meta.xrp_balance_change(OTHER),
+            Some(BalanceChange { delta: 1_000_000, balance: Drops(51_000_000) })
+        );
+    }
+
+    #[test]
+    fn unchanged_balance_is_none() {
+        let meta = meta(json!([account_root(ACCOUNT, None, "100000000")]));
+        assert_eq!(meta.xrp_balance_change(ACCOUNT), None);
+    }
+
+    #[test]
+    fn unaffected_account_is_none() {
+        let meta = meta(json!([account_root(OTHER, Some("1"), "2")]));
+        assert_eq!(meta.xrp_balance_change(ACCOUNT), None);
+    }
+
+    #[test]
+    fn created_account_starts_from_zero() {
+        let meta = meta(json!([{ "CreatedNode": {
+            "LedgerEntryType": "AccountRoot",
+            "LedgerIndex": "00",
+            "NewFields": { "Account": ACCOUNT, "Balance": "10000000" },
+        } }]));
+        assert_eq!(
+            meta.xrp_balance_change(ACCOUNT),
+            Some(BalanceChange { delta: 10_000_000, balance: Drops(10_000_000) })
+        );
+    }
+
+    #[test]
+    fn ignores_other_ledger_entries() {
+        let meta = meta(json!([{ "ModifiedNode": {
+            "LedgerEntryType": "RippleState",
+            "LedgerIndex": "00",
+            "FinalFields": { "Account": ACCOUNT, "Balance": "5" },
+            "PreviousFields": { "Balance": "1" },
+        } }]));
+        assert_eq!(meta.xrp_balance_change(ACCOUNT), None);
+        assert!(meta.mentions(ACCOUNT));
+    }
+}
@@ -1,4 +1,16 @@
+use chrono::{DateTime, SecondsFormat, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::address::AddressInfo;
+use crate::amount::{self, Amount, AmountError, Asset, Currency, Drops};
+use crate::codec;
+use crate::meta::{AffectedNode, TransactionMeta};
+use crate::network::Network;
+use crate::output::Record;
+use crate::rippled_error::RippledError;
+use crate::time;
+use crate::transaction::{Memo, Transaction};
 
 #[derive(Serialize, Debug)]
 pub struct AccountInfoRequest {
@@ -34,20 +46,240 @@ pub struct AccountTxRequest {
 pub struct AccountTxParams {
     pub account: String,
     pub limit: u32,
-    pub descending: bool,
+    pub forward: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ledger_index_min: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ledger_index_max: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub marker: Option<Value>,
 }
 
 impl AccountTxRequest {
     pub fn new(account: String) -> Self {
+        Self::page(account, &AccountTxQuery::default(), 1, None)
+    }
+
+    pub fn page(account: String, query: &AccountTxQuery, limit: u32, marker: Option<Value>) -> Self {
         AccountTxRequest {
             method: "account_tx".to_string(),
             params: vec![AccountTxParams {
                 account,
-                limit: 1,
-                descending: true,
+                limit,
+                forward: query.forward,
+                ledger_index_min: query.ledger_index_min,
+                ledger_index_max: query.ledger_index_max,
+                marker,
+            }],
+        }
+    }
+}
+
+/// Параметры выборки истории транзакций
+#[derive(Debug, Clone, Default)]
+pub struct AccountTxQuery {
+    /// Максимальное общее количество транзакций (None - без ограничения)
+    pub limit: Option<u32>,
+    /// Минимальный индекс леджера (-1 - самый ранний доступный)
+    pub ledger_index_min: Option<i64>,
+    /// Максимальный индекс леджера (-1 - последний валидированный)
+    pub ledger_index_max: Option<i64>,
+    /// Сортировка от старых к новым
+    pub forward: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TxRequest {
+    pub method: String,
+    pub params: Vec<TxParams>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TxParams {
+    pub transaction: String,
+    pub binary: bool,
+}
+
+impl TxRequest {
+    pub fn new(hash: String) -> Self {
+        TxRequest {
+            method: "tx".to_string(),
+            params: vec![TxParams {
+                transaction: hash,
+                binary: false,
+            }],
+        }
+    }
+}
+
+/// Параметры постраничных запросов по аккаунту
+/// (account_lines, account_offers, account_nfts, account_objects)
+#[derive(Serialize, Debug)]
+pub struct AccountPageParams {
+    pub account: String,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub object_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub marker: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ledger_index: Option<u32>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct AccountPageRequest {
+    pub method: String,
+    pub params: Vec<AccountPageParams>,
+}
+
+impl AccountPageRequest {
+    pub fn new(method: &str, account: String, limit: Option<u32>, marker: Option<Value>) -> Self {
+        AccountPageRequest {
+            method: method.to_string(),
+            params: vec![AccountPageParams {
+                account,
+                object_type: None,
+                limit,
+                marker,
+                ledger_index: None,
             }],
         }
     }
+
+    /// Запрашивает страницу из леджера `ledger_index` (None - текущий леджер сервера)
+    pub fn at_ledger(mut self, ledger_index: Option<u32>) -> Self {
+        for params in &mut self.params {
+            params.ledger_index = ledger_index;
+        }
+        self
+    }
+
+    /// Ограничивает account_objects объектами одного типа
+    pub fn with_object_type(mut self, object_type: Option<String>) -> Self {
+        for params in &mut self.params {
+            params.object_type = object_type.clone();
+        }
+        self
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct LedgerRequest {
+    pub method: String,
+    pub params: Vec<LedgerParams>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct LedgerParams {
+    /// Номер леджера или "validated" / "closed" / "current"
+    pub ledger_index: Value,
+}
+
+impl LedgerRequest {
+    pub fn new(ledger_index: Value) -> Self {
+        LedgerRequest {
+            method: "ledger".to_string(),
+            params: vec![LedgerParams { ledger_index }],
+        }
+    }
+}
+
+/// Предложения книги заявок: taker_gets - что получит исполнитель, taker_pays - что отдаст
+#[derive(Serialize, Debug)]
+pub struct BookOffersRequest {
+    pub method: String,
+    pub params: Vec<BookOffersParams>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct BookOffersParams {
+    pub taker_gets: Asset,
+    pub taker_pays: Asset,
+    pub limit: u32,
+    pub ledger_index: String,
+}
+
+impl BookOffersRequest {
+    pub fn new(taker_gets: Asset, taker_pays: Asset, limit: u32) -> Self {
+        BookOffersRequest {
+            method: "book_offers".to_string(),
+            params: vec![BookOffersParams {
+                taker_gets,
+                taker_pays,
+                limit,
+                ledger_index: "validated".to_string(),
+            }],
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct ServerInfoRequest {
+    pub method: String,
+    pub params: Vec<Value>,
+}
+
+impl ServerInfoRequest {
+    pub fn new() -> Self {
+        ServerInfoRequest {
+            method: "server_info".to_string(),
+            params: vec![Value::Object(Default::default())],
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct ServerStateRequest {
+    pub method: String,
+    pub params: Vec<Value>,
+}
+
+impl ServerStateRequest {
+    pub fn new() -> Self {
+        ServerStateRequest {
+            method: "server_state".to_string(),
+            params: vec![Value::Object(Default::default())],
+        }
+    }
+}
+
+/// Подписка на закрытие леджеров и транзакции счетов (только WebSocket)
+#[derive(Serialize, Debug)]
+pub struct SubscribeRequest {
+    pub method: String,
+    pub params: Vec<SubscribeParams>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SubscribeParams {
+    pub streams: Vec<String>,
+    pub accounts: Vec<String>,
+}
+
+impl SubscribeRequest {
+    pub fn new(accounts: Vec<String>) -> Self {
+        SubscribeRequest {
+            method: "subscribe".to_string(),
+            params: vec![SubscribeParams {
+                streams: vec!["ledger".to_string()],
+                accounts,
+            }],
+        }
+    }
+}
+
+/// Ответ rippled с ошибкой (`"status": "error"` в `result`)
+#[derive(Deserialize, Debug, Clone)]
+pub struct ErrorResponse {
+    pub result: ErrorResult,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ErrorResult {
+    pub error: RippledError,
+    pub error_code: Option<i32>,
+    pub error_message: Option<String>,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -58,24 +290,140 @@ pub struct AccountInfoResponse {
 #[derive(Deserialize, Debug, Clone)]
 pub struct AccountInfoResult {
     pub account_data: AccountData,
-    pub status: String,
 }
 
+/// Объект AccountRoot из account_info
 #[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "PascalCase")]
 pub struct AccountData {
-    #[serde(rename = "Balance")]
-    pub balance: String,
-    // #[serde(rename = "Account")]
-    // pub account: String,
+    pub account: String,
+    pub balance: Drops,
+    /// Число объектов леджера, за которые аккаунт платит резерв
+    #[serde(default)]
+    pub owner_count: u32,
+    pub sequence: u32,
+    #[serde(default)]
+    pub flags: u32,
+    /// Последняя транзакция, изменившая AccountRoot
+    #[serde(rename = "PreviousTxnID")]
+    pub previous_txn_id: Option<String>,
+    pub previous_txn_lgr_seq: Option<u32>,
+    /// Хеш последней отправленной транзакции (если включен asfAccountTxnID)
+    #[serde(rename = "AccountTxnID")]
+    pub account_txn_id: Option<String>,
+    /// Альтернативный ключ подписи
+    pub regular_key: Option<String>,
+    /// Домен владельца в hex
+    pub domain: Option<String>,
+    /// MD5-хеш email для Gravatar
+    pub email_hash: Option<String>,
+    /// Публичный ключ для зашифрованных сообщений
+    pub message_key: Option<String>,
+    /// Комиссия за перевод токенов эмитента в миллиардных долях (1000000000 - без комиссии)
+    pub transfer_rate: Option<u32>,
+    /// Число значащих цифр курса в предложениях на токены эмитента
+    pub tick_size: Option<u8>,
+    /// Аккаунт, которому разрешено выпускать NFT от имени этого
+    #[serde(rename = "NFTokenMinter")]
+    pub nftoken_minter: Option<String>,
+    #[serde(rename = "MintedNFTokens")]
+    pub minted_nftokens: Option<u32>,
+    #[serde(rename = "BurnedNFTokens")]
+    pub burned_nftokens: Option<u32>,
+    #[serde(rename = "FirstNFTokenSequence")]
+    pub first_nftoken_sequence: Option<u32>,
+    /// Число неиспользованных билетов (Ticket)
+    pub ticket_count: Option<u32>,
+    /// Пул AMM, которому принадлежит специальный аккаунт
+    #[serde(rename = "AMMID")]
+    pub amm_id: Option<String>,
+    pub wallet_locator: Option<String>,
 }
 
-impl AccountData {
-    pub fn balance_xrp(&self) -> f64 {
-        match self.balance.parse::<f64>() {
-            Ok(balance_drops) => balance_drops / 1_000_000.0,
-            Err(_) => 0.0,
+/// Флаги AccountRoot (lsf*) в виде именованных признаков
+#[derive(Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AccountFlags {
+    #[serde(rename = "lsfPasswordSpent")]
+    pub password_spent: bool,
+    #[serde(rename = "lsfRequireDestTag")]
+    pub require_dest_tag: bool,
+    #[serde(rename = "lsfRequireAuth")]
+    pub require_auth: bool,
+    #[serde(rename = "lsfDisallowXRP")]
+    pub disallow_xrp: bool,
+    #[serde(rename = "lsfDisableMaster")]
+    pub disable_master: bool,
+    #[serde(rename = "lsfNoFreeze")]
+    pub no_freeze: bool,
+    #[serde(rename = "lsfGlobalFreeze")]
+    pub global_freeze: bool,
+    #[serde(rename = "lsfDefaultRipple")]
+    pub default_ripple: bool,
+    #[serde(rename = "lsfDepositAuth")]
+    pub deposit_auth: bool,
+    #[serde(rename = "lsfAMM")]
+    pub amm: bool,
+    #[serde(rename = "lsfDisallowIncomingNFTokenOffer")]
+    pub disallow_incoming_nftoken_offer: bool,
+    #[serde(rename = "lsfDisallowIncomingCheck")]
+    pub disallow_incoming_check: bool,
+    #[serde(rename = "lsfDisallowIncomingPayChan")]
+    pub disallow_incoming_pay_chan: bool,
+    #[serde(rename = "lsfDisallowIncomingTrustline")]
+    pub disallow_incoming_trustline: bool,
+    #[serde(rename = "lsfAllowTrustLineLocking")]
+    pub allow_trust_line_locking: bool,
+    #[serde(rename = "lsfAllowTrustLineClawback")]
+    pub allow_trust_line_clawback: bool,
+}
+
+impl AccountFlags {
+    pub fn from_bits(flags: u32) -> Self {
+        let set = |bit: u32| flags & bit != 0;
+        AccountFlags {
+            password_spent: set(0x0001_0000),
+            require_dest_tag: set(0x0002_0000),
+            require_auth: set(0x0004_0000),
+            disallow_xrp: set(0x0008_0000),
+            disable_master: set(0x0010_0000),
+            no_freeze: set(0x0020_0000),
+            global_freeze: set(0x0040_0000),
+            default_ripple: set(0x0080_0000),
+            deposit_auth: set(0x0100_0000),
+            amm: set(0x0200_0000),
+            disallow_incoming_nftoken_offer: set(0x0400_0000),
+            disallow_incoming_check: set(0x0800_0000),
+            disallow_incoming_pay_chan: set(0x1000_0000),
+            disallow_incoming_trustline: set(0x2000_0000),
+            allow_trust_line_locking: set(0x4000_0000),
+            allow_trust_line_clawback: set(0x8000_0000),
         }
     }
+
+    /// Имена установленных флагов
+    pub fn names(&self) -> Vec<&'static str> {
+        [
+            (self.password_spent, "lsfPasswordSpent"),
+            (self.require_dest_tag, "lsfRequireDestTag"),
+            (self.require_auth, "lsfRequireAuth"),
+            (self.disallow_xrp, "lsfDisallowXRP"),
+            (self.disable_master, "lsfDisableMaster"),
+            (self.no_freeze, "lsfNoFreeze"),
+            (self.global_freeze, "lsfGlobalFreeze"),
+            (self.default_ripple, "lsfDefaultRipple"),
+            (self.deposit_auth, "lsfDepositAuth"),
+            (self.amm, "lsfAMM"),
+            (self.disallow_incoming_nftoken_offer, "lsfDisallowIncomingNFTokenOffer"),
+            (self.disallow_incoming_check, "lsfDisallowIncomingCheck"),
+            (self.disallow_incoming_pay_chan, "lsfDisallowIncomingPayChan"),
+            (self.disallow_incoming_trustline, "lsfDisallowIncomingTrustline"),
+            (self.allow_trust_line_locking, "lsfAllowTrustLineLocking"),
+            (self.allow_trust_line_clawback, "lsfAllowTrustLineClawback"),
+        ]
+        .into_iter()
+        .filter_map(|(set, name)| set.then_some(name))
+        .collect()
+    }
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -86,113 +434,1585 @@ pub struct AccountTxResponse {
 #[derive(Deserialize, Debug, Clone)]
 pub struct AccountTxResult {
     pub transactions: Vec<TransactionWrapper>,
-    pub status: String,
+    /// Маркер следующей страницы (отсутствует на последней странице)
+    pub marker: Option<Value>,
+    /// Фактический диапазон леджеров выборки (с учетом -1 и доступной истории сервера)
+    pub ledger_index_min: Option<u32>,
+    pub ledger_index_max: Option<u32>,
 }
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct TransactionWrapper {
     pub tx: Transaction,
+    pub meta: Option<TransactionMeta>,
+}
+
+impl TransactionWrapper {
+    pub fn display(&self) -> DisplayTransaction {
+        DisplayTransaction::new(&self.tx, self.meta.as_ref())
+    }
 }
 
 #[derive(Deserialize, Debug, Clone)]
-pub struct Transaction {
-    pub hash: String,
-    #[serde(rename = "Amount")]
-    pub amount: Option<String>,
-    pub date: Option<u64>,
-    #[serde(rename = "Account")]
-    pub account: String,
-    #[serde(rename = "Destination")]
-    pub destination: Option<String>,
-    #[serde(rename = "TransactionType")]
-    pub transaction_type: String,
+pub struct SubscribeResponse {
+    pub result: SubscribeResult,
 }
 
-impl Transaction {
-    pub fn amount_xrp(&self) -> f64 {
-        match &self.amount {
-            Some(amount_str) => {
-                match amount_str.parse::<f64>() {
-                    Ok(amount_drops) => amount_drops / 1_000_000.0,
-                    Err(_) => 0.0,
-                }
+#[derive(Deserialize, Debug, Clone)]
+pub struct SubscribeResult {
+    /// Последний валидированный леджер на момент подписки (при подписке на поток ledger)
+    pub ledger_index: Option<u32>,
+}
+
+/// Сообщение потока подписки
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type")]
+pub enum StreamMessage {
+    #[serde(rename = "ledgerClosed")]
+    LedgerClosed { ledger_index: u32 },
+    #[serde(rename = "transaction")]
+    Transaction(Box<TransactionStreamEvent>),
+    /// Прочие потоки (на них программа не подписывается)
+    #[serde(other)]
+    Other,
+}
+
+/// Транзакция из потока accounts
+#[derive(Deserialize, Debug)]
+pub struct TransactionStreamEvent {
+    /// API v2 передает транзакцию в `tx_json`, а хеш - отдельно
+    #[serde(alias = "tx_json")]
+    transaction: Value,
+    hash: Option<String>,
+    ledger_index: Option<u32>,
+    meta: Option<TransactionMeta>,
+    #[serde(default)]
+    pub validated: bool,
+}
+
+impl TransactionStreamEvent {
+    /// Транзакция в том же виде, что и в ответе account_tx
+    pub fn into_wrapper(self) -> Result<TransactionWrapper, serde_json::Error> {
+        let mut tx = self.transaction;
+        if let Value::Object(fields) = &mut tx {
+            if let Some(hash) = self.hash {
+                fields.entry("hash").or_insert(hash.into());
+            }
+            if let Some(ledger_index) = self.ledger_index {
+                fields.entry("ledger_index").or_insert(ledger_index.into());
             }
-            None => 0.0,
         }
+        Ok(TransactionWrapper {
+            tx: Transaction::try_from(tx)?,
+            meta: self.meta,
+        })
     }
+}
 
-    pub fn formatted_date(&self) -> String {
-        match self.date {
-            Some(timestamp) => {
-                let ripple_epoch = 946_684_800;
-                let unix_timestamp = ripple_epoch + timestamp;
-                format_timestamp(unix_timestamp)
-            }
-            None => "Нет данных".to_string(),
-        }
+#[derive(Deserialize, Debug, Clone)]
+pub struct TxResponse {
+    pub result: TxResult,
+}
+
+/// Результат метода tx: поля транзакции, `meta` и `validated` на одном уровне
+#[derive(Deserialize, Debug, Clone)]
+#[serde(try_from = "Value")]
+pub struct TxResult {
+    pub tx: Transaction,
+    pub meta: Option<TransactionMeta>,
+    pub validated: bool,
+}
+
+impl TryFrom<Value> for TxResult {
+    type Error = serde_json::Error;
+
+    fn try_from(mut value: Value) -> Result<Self, Self::Error> {
+        let meta = match value.get_mut("meta").map(Value::take) {
+            Some(meta @ Value::Object(_)) => Some(serde_json::from_value(meta)?),
+            _ => None,
+        };
+        let validated = value["validated"].as_bool().unwrap_or(false);
+
+        Ok(TxResult {
+            tx: Transaction::try_from(value)?,
+            meta,
+            validated,
+        })
     }
 }
 
-fn format_timestamp(timestamp: u64) -> String {
-    // use std::time::{Duration, SystemTime, UNIX_EPOCH};
-    use std::time::{Duration, UNIX_EPOCH};
-    
-    let dt = UNIX_EPOCH + Duration::from_secs(timestamp);
-    
-    match dt.duration_since(UNIX_EPOCH) {
-        Ok(_) => {
-            let secs = timestamp;
-            let days = secs / 86400;
-            let hours = (secs % 86400) / 3600;
-            let minutes = (secs % 3600) / 60;
-            
-            format!("20{}-{:02}-{:02} {:02}:{:02} UTC", 
-                   24 + (days / 365), 
-                   ((days % 365) / 30) + 1, 
-                   (days % 30) + 1,
-                   hours, 
-                   minutes)
-        }
-        Err(_) => "Некорректная дата".to_string(),
+#[derive(Deserialize, Debug, Clone)]
+pub struct AccountLinesResponse {
+    pub result: AccountLinesResult,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AccountLinesResult {
+    pub lines: Vec<TrustLine>,
+    pub marker: Option<Value>,
+    #[serde(flatten)]
+    pub ledger: PageLedger,
+}
+
+/// Леджер, по которому сервер сформировал страницу ответа
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct PageLedger {
+    /// Для закрытого или валидированного леджера
+    pub ledger_index: Option<u32>,
+    /// Для текущего (открытого) леджера
+    pub ledger_current_index: Option<u32>,
+}
+
+impl PageLedger {
+    /// Номер леджера, к которому нужно привязать следующие страницы,
+    /// чтобы все страницы описывали одно и то же состояние
+    pub fn index(&self) -> Option<u32> {
+        self.ledger_index.or(self.ledger_current_index)
     }
 }
 
+/// Первая страница линий доверия
 #[derive(Debug, Clone)]
+pub struct TrustLinePage {
+    pub lines: Vec<TrustLine>,
+    /// У аккаунта есть линии сверх этой страницы
+    pub truncated: bool,
+}
+
+/// Линия доверия с точки зрения запрошенного аккаунта
+#[derive(Deserialize, Debug, Clone)]
+pub struct TrustLine {
+    /// Контрагент (эмитент токена)
+    pub account: String,
+    pub balance: String,
+    pub currency: Currency,
+    pub limit: String,
+    pub limit_peer: String,
+    /// Флаг No Ripple на своей стороне линии
+    #[serde(default)]
+    pub no_ripple: bool,
+    #[serde(default)]
+    pub no_ripple_peer: bool,
+    /// Эмитент разрешил аккаунту держать токен (RequireAuth)
+    #[serde(default)]
+    pub authorized: bool,
+    #[serde(default)]
+    pub peer_authorized: bool,
+    /// Аккаунт заморозил линию
+    #[serde(default)]
+    pub freeze: bool,
+    /// Контрагент заморозил линию
+    #[serde(default)]
+    pub freeze_peer: bool,
+    /// Курс входящих переводов в миллиардных долях (0 - по номиналу)
+    #[serde(default)]
+    pub quality_in: u32,
+    /// Курс исходящих переводов в миллиардных долях (0 - по номиналу)
+    #[serde(default)]
+    pub quality_out: u32,
+}
+
+impl TrustLine {
+    /// Установленные флаги линии в виде имен
+    pub fn flag_names(&self) -> Vec<&'static str> {
+        [
+            (self.no_ripple, "no_ripple"),
+            (self.no_ripple_peer, "no_ripple_peer"),
+            (self.authorized, "authorized"),
+            (self.peer_authorized, "peer_authorized"),
+            (self.freeze, "freeze"),
+            (self.freeze_peer, "freeze_peer"),
+        ]
+        .into_iter()
+        .filter_map(|(set, name)| set.then_some(name))
+        .collect()
+    }
+
+    /// Ненулевой баланс (отрицательный - токены, выпущенные аккаунтом)
+    pub fn has_balance(&self) -> bool {
+        is_nonzero(&self.balance)
+    }
+
+    /// Контрагент установил ненулевой лимит со своей стороны
+    pub fn has_peer_limit(&self) -> bool {
+        is_nonzero(&self.limit_peer)
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AccountOffersResponse {
+    pub result: AccountOffersResult,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AccountOffersResult {
+    pub offers: Vec<AccountOffer>,
+    pub marker: Option<Value>,
+    #[serde(flatten)]
+    pub ledger: PageLedger,
+}
+
+/// Флаг предложения: не исполняется против встречных предложений по той же цене
+pub const LSF_PASSIVE: u32 = 0x0001_0000;
+/// Флаг предложения: продать весь taker_gets, даже если выручка превысит taker_pays
+pub const LSF_SELL: u32 = 0x0002_0000;
+
+/// Открытое предложение на DEX
+#[derive(Deserialize, Debug, Clone)]
+pub struct AccountOffer {
+    pub flags: u32,
+    pub seq: u32,
+    pub taker_gets: Amount,
+    pub taker_pays: Amount,
+    pub quality: String,
+    pub expiration: Option<u32>,
+}
+
+impl AccountOffer {
+    /// Цена в единицах taker_pays за единицу taker_gets
+    pub fn price(&self) -> Option<f64> {
+        amount::price(&self.taker_gets, &self.taker_pays)
+    }
+
+    pub fn is_passive(&self) -> bool {
+        self.flags & LSF_PASSIVE != 0
+    }
+
+    pub fn is_sell(&self) -> bool {
+        self.flags & LSF_SELL != 0
+    }
+
+    pub fn expiration(&self) -> Option<DateTime<Utc>> {
+        self.expiration.and_then(|e| time::ripple_to_datetime(e as u64))
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct BookOffersResponse {
+    pub result: BookOffersResult,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct BookOffersResult {
+    pub offers: Vec<BookOffer>,
+}
+
+/// Предложение из книги заявок (объект Offer с полями обеспечения)
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct BookOffer {
+    pub account: String,
+    pub sequence: u32,
+    pub taker_gets: Amount,
+    pub taker_pays: Amount,
+    /// Часть TakerGets, обеспеченная балансом владельца (если меньше заявленной)
+    #[serde(rename = "taker_gets_funded")]
+    pub taker_gets_funded: Option<Amount>,
+    #[serde(rename = "taker_pays_funded")]
+    pub taker_pays_funded: Option<Amount>,
+}
+
+impl BookOffer {
+    /// Обеспеченная часть TakerGets
+    pub fn funded_gets(&self) -> &Amount {
+        self.taker_gets_funded.as_ref().unwrap_or(&self.taker_gets)
+    }
+
+    /// Обеспеченная часть TakerPays
+    pub fn funded_pays(&self) -> &Amount {
+        self.taker_pays_funded.as_ref().unwrap_or(&self.taker_pays)
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AccountNftsResponse {
+    pub result: AccountNftsResult,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AccountNftsResult {
+    pub account_nfts: Vec<NfToken>,
+    pub marker: Option<Value>,
+    #[serde(flatten)]
+    pub ledger: PageLedger,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct NfToken {
+    #[serde(rename = "Flags")]
+    pub flags: u32,
+    #[serde(rename = "Issuer")]
+    pub issuer: String,
+    #[serde(rename = "NFTokenID")]
+    pub nftoken_id: String,
+    #[serde(rename = "NFTokenTaxon")]
+    pub nftoken_taxon: u32,
+    #[serde(rename = "URI")]
+    pub uri: Option<String>,
+    #[serde(rename = "TransferFee")]
+    pub transfer_fee: Option<u16>,
+    pub nft_serial: u32,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AccountObjectsResponse {
+    pub result: AccountObjectsResult,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AccountObjectsResult {
+    /// Объекты леджера разных типов, поэтому без строгой типизации
+    pub account_objects: Vec<Value>,
+    pub marker: Option<Value>,
+    #[serde(flatten)]
+    pub ledger: PageLedger,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct LedgerResponse {
+    pub result: LedgerResult,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct LedgerResult {
+    pub ledger: LedgerHeader,
+    /// Отсутствует у открытого (current) леджера
+    pub ledger_hash: Option<String>,
+    pub ledger_index: Option<u32>,
+    pub ledger_current_index: Option<u32>,
+    #[serde(default)]
+    pub validated: bool,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct LedgerHeader {
+    pub close_time: Option<u64>,
+    pub parent_hash: String,
+    pub total_coins: Drops,
+    #[serde(default)]
+    pub closed: bool,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ServerInfoResponse {
+    pub result: ServerInfoResult,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ServerInfoResult {
+    pub info: ServerInfo,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ServerInfo {
+    pub build_version: String,
+    pub server_state: String,
+    pub complete_ledgers: String,
+    pub network_id: Option<u32>,
+    pub peers: Option<u32>,
+    pub uptime: Option<u64>,
+    pub load_factor: Option<f64>,
+    pub validated_ledger: Option<ValidatedLedger>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ServerStateResponse {
+    pub result: ServerStateResult,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ServerStateResult {
+    pub state: ServerState,
+}
+
+/// Состояние сервера в машинном виде (server_state): суммы в drops, а не в XRP
+#[derive(Deserialize, Debug, Clone)]
+pub struct ServerState {
+    pub validated_ledger: Option<ValidatedLedgerState>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ValidatedLedgerState {
+    /// Базовый резерв, drops
+    pub reserve_base: u64,
+    /// Резерв за каждый объект, drops
+    pub reserve_inc: u64,
+}
+
+impl ValidatedLedgerState {
+    pub fn reserves(&self) -> Reserves {
+        Reserves {
+            base: Drops(self.reserve_base),
+            owner: Drops(self.reserve_inc),
+        }
+    }
+}
+
+/// Резервы сети: часть баланса, которую нельзя потратить
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reserves {
+    pub base: Drops,
+    /// За каждый объект, которым владеет аккаунт (OwnerCount)
+    pub owner: Drops,
+}
+
+impl Reserves {
+    /// Резерв аккаунта: base + owner × owner_count
+    pub fn for_owner_count(&self, owner_count: u32) -> Result<Drops, AmountError> {
+        self.base.checked_add(self.owner.checked_mul(u64::from(owner_count))?)
+    }
+}
+
+/// Последний валидированный леджер (значения в XRP, как их отдает server_info)
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ValidatedLedger {
+    pub seq: u32,
+    pub hash: String,
+    pub age: u64,
+    pub base_fee_xrp: f64,
+    pub reserve_base_xrp: f64,
+    pub reserve_inc_xrp: f64,
+}
+
+#[derive(Serialize, Debug, Clone)]
 pub struct DisplayAccountInfo {
     pub address: String,
-    pub balance_xrp: f64,
+    /// X-адрес в форме выбранной сети (None в сети custom)
+    pub x_address: Option<String>,
+    #[serde(rename = "balance_drops")]
+    pub balance: Drops,
+    /// Зарезервированная часть баланса (None, если резервы сети неизвестны)
+    #[serde(rename = "reserved_drops")]
+    pub reserved: Option<Drops>,
+    /// Доступно для отправки: баланс за вычетом резерва
+    #[serde(rename = "available_drops")]
+    pub available: Option<Drops>,
+    pub owner_count: u32,
+    pub sequence: u32,
+    pub flags: u32,
+    pub account_flags: AccountFlags,
+    pub previous_txn_id: Option<String>,
+    pub previous_txn_lgr_seq: Option<u32>,
+    pub account_txn_id: Option<String>,
+    pub regular_key: Option<String>,
+    /// Домен, декодированный из hex (если это не текст - hex как есть)
+    pub domain: Option<String>,
+    pub email_hash: Option<String>,
+    pub message_key: Option<String>,
+    pub transfer_rate: Option<u32>,
+    pub tick_size: Option<u8>,
+    pub nftoken_minter: Option<String>,
+    pub minted_nftokens: Option<u32>,
+    pub burned_nftokens: Option<u32>,
+    pub first_nftoken_sequence: Option<u32>,
+    pub ticket_count: Option<u32>,
+    pub amm_id: Option<String>,
+    pub wallet_locator: Option<String>,
+    /// Число загруженных линий доверия (None, если их не удалось получить)
+    pub trust_lines: Option<usize>,
+    /// Токены с ненулевым балансом
+    pub tokens: Vec<DisplayTokenBalance>,
+    /// Загружена только первая страница линий: список токенов может быть неполным
+    pub tokens_truncated: bool,
+    pub last_transaction: Option<DisplayTransaction>,
+}
+
+/// Баланс токена в сводке по кошельку
+#[derive(Serialize, Debug, Clone)]
+pub struct DisplayTokenBalance {
+    pub currency: String,
+    pub issuer: String,
+    pub balance: String,
+}
+
+impl DisplayTokenBalance {
+    pub fn from_trust_line(line: &TrustLine) -> Self {
+        DisplayTokenBalance {
+            currency: line.currency.display_name(),
+            issuer: line.account.clone(),
+            balance: line.balance.clone(),
+        }
+    }
 }
 
 impl DisplayAccountInfo {
-    pub fn from_account_data(address: String, account_data: &AccountData) -> Self {
+    pub fn from_account_data(address: &AddressInfo, network: Network, account_data: &AccountData) -> Self {
         DisplayAccountInfo {
-            address,
-            balance_xrp: account_data.balance_xrp(),
+            address: account_data.account.clone(),
+            x_address: address.x_address(network),
+            balance: account_data.balance,
+            reserved: None,
+            available: None,
+            owner_count: account_data.owner_count,
+            sequence: account_data.sequence,
+            flags: account_data.flags,
+            account_flags: AccountFlags::from_bits(account_data.flags),
+            previous_txn_id: account_data.previous_txn_id.clone(),
+            previous_txn_lgr_seq: account_data.previous_txn_lgr_seq,
+            account_txn_id: account_data.account_txn_id.clone(),
+            regular_key: account_data.regular_key.clone(),
+            domain: account_data
+                .domain
+                .as_deref()
+                .map(|domain| codec::decode_hex_text(domain).unwrap_or_else(|| domain.to_string())),
+            email_hash: account_data.email_hash.clone(),
+            message_key: account_data.message_key.clone(),
+            transfer_rate: account_data.transfer_rate,
+            tick_size: account_data.tick_size,
+            nftoken_minter: account_data.nftoken_minter.clone(),
+            minted_nftokens: account_data.minted_nftokens,
+            burned_nftokens: account_data.burned_nftokens,
+            first_nftoken_sequence: account_data.first_nftoken_sequence,
+            ticket_count: account_data.ticket_count,
+            amm_id: account_data.amm_id.clone(),
+            wallet_locator: account_data.wallet_locator.clone(),
+            trust_lines: None,
+            tokens: Vec::new(),
+            tokens_truncated: false,
+            last_transaction: None,
+        }
+    }
+
+    /// Резерв и доступный остаток; баланс может быть меньше резерва, если резервы сети выросли
+    pub fn with_reserves(mut self, reserves: Option<&Reserves>) -> Result<Self, AmountError> {
+        if let Some(reserves) = reserves {
+            let reserved = reserves.for_owner_count(self.owner_count)?;
+            self.reserved = Some(reserved);
+            self.available = Some(self.balance.saturating_sub(reserved));
         }
+        Ok(self)
+    }
+
+    pub fn with_trust_lines(mut self, page: Option<&TrustLinePage>) -> Self {
+        let Some(page) = page else {
+            return self;
+        };
+        self.trust_lines = Some(page.lines.len());
+        self.tokens_truncated = page.truncated;
+        self.tokens = page
+            .lines
+            .iter()
+            .filter(|line| line.has_balance())
+            .map(DisplayTokenBalance::from_trust_line)
+            .collect();
+        self
+    }
+
+    pub fn with_last_transaction(mut self, transaction: Option<&TransactionWrapper>) -> Self {
+        self.last_transaction = transaction.map(TransactionWrapper::display);
+        self
     }
 }
 
-#[derive(Debug, Clone)]
+impl Record for DisplayAccountInfo {
+    const KIND: &'static str = "account";
+
+    fn csv_columns(&self) -> Vec<(&'static str, String)> {
+        let last = self.last_transaction.as_ref();
+        let tokens: Vec<String> = self
+            .tokens
+            .iter()
+            .map(|token| format!("{} {}.{}", token.balance, token.currency, token.issuer))
+            .collect();
+        vec![
+            ("address", self.address.clone()),
+            ("x_address", self.x_address.clone().unwrap_or_default()),
+            ("balance_drops", self.balance.0.to_string()),
+            ("balance_xrp", self.balance.to_string()),
+            ("reserved_drops", self.reserved.map(|d| d.0.to_string()).unwrap_or_default()),
+            ("available_drops", self.available.map(|d| d.0.to_string()).unwrap_or_default()),
+            ("owner_count", self.owner_count.to_string()),
+            ("sequence", self.sequence.to_string()),
+            ("flags", self.flags.to_string()),
+            ("account_flags", self.account_flags.names().join("; ")),
+            ("previous_txn_id", self.previous_txn_id.clone().unwrap_or_default()),
+            ("previous_txn_lgr_seq", self.previous_txn_lgr_seq.map(|v| v.to_string()).unwrap_or_default()),
+            ("account_txn_id", self.account_txn_id.clone().unwrap_or_default()),
+            ("regular_key", self.regular_key.clone().unwrap_or_default()),
+            ("domain", self.domain.clone().unwrap_or_default()),
+            ("email_hash", self.email_hash.clone().unwrap_or_default()),
+            ("message_key", self.message_key.clone().unwrap_or_default()),
+            ("transfer_rate", self.transfer_rate.map(|v| v.to_string()).unwrap_or_default()),
+            ("tick_size", self.tick_size.map(|v| v.to_string()).unwrap_or_default()),
+            ("nftoken_minter", self.nftoken_minter.clone().unwrap_or_default()),
+            ("minted_nftokens", self.minted_nftokens.map(|v| v.to_string()).unwrap_or_default()),
+            ("burned_nftokens", self.burned_nftokens.map(|v| v.to_string()).unwrap_or_default()),
+            ("first_nftoken_sequence", self.first_nftoken_sequence.map(|v| v.to_string()).unwrap_or_default()),
+            ("ticket_count", self.ticket_count.map(|v| v.to_string()).unwrap_or_default()),
+            ("amm_id", self.amm_id.clone().unwrap_or_default()),
+            ("wallet_locator", self.wallet_locator.clone().unwrap_or_default()),
+            ("trust_lines", self.trust_lines.map(|n| n.to_string()).unwrap_or_default()),
+            ("tokens", tokens.join("; ")),
+            ("tokens_truncated", self.tokens_truncated.to_string()),
+            ("last_tx_hash", last.map(|tx| tx.hash.clone()).unwrap_or_default()),
+            ("last_tx_type", last.map(|tx| tx.transaction_type.clone()).unwrap_or_default()),
+            ("last_tx_timestamp", last.map(DisplayTransaction::timestamp_rfc3339).unwrap_or_default()),
+        ]
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
 pub struct DisplayTransaction {
     pub hash: String,
-    pub amount_xrp: f64,
-    pub timestamp: String,
+    pub transaction_type: String,
+    /// Доставленная сумма из метаданных, иначе поле Amount.
+    /// None у частичного платежа без delivered_amount: доставленная сумма неизвестна
+    pub amount: Option<Amount>,
+    /// Частичный платеж (флаг tfPartialPayment): Amount - лишь верхняя граница суммы
+    pub partial_payment: bool,
+    pub timestamp: Option<DateTime<Utc>>,
     pub from: String,
-    pub to: String,
+    pub to: Option<String>,
+    #[serde(rename = "fee_drops")]
+    pub fee: Option<Drops>,
+    pub sequence: Option<u32>,
+    pub ledger_index: Option<u32>,
 }
 
 impl DisplayTransaction {
-    pub fn from_transaction(tx: &Transaction) -> Option<Self> {
-        if tx.transaction_type == "Payment" {
-            Some(DisplayTransaction {
-                hash: tx.hash.clone(),
-                amount_xrp: tx.amount_xrp(),
-                timestamp: tx.formatted_date(),
-                from: tx.account.clone(),
-                to: tx.destination.clone().unwrap_or_else(|| "Неизвестно".to_string()),
+    /// Запись транзакции; сумма берется из delivered_amount метаданных, если они есть.
+    /// Amount частичного платежа за доставленную сумму не выдается.
+    pub fn new(tx: &Transaction, meta: Option<&TransactionMeta>) -> Self {
+        let delivered = meta.and_then(TransactionMeta::delivered_amount);
+        let partial_payment = tx.is_partial_payment();
+        DisplayTransaction {
+            hash: tx.hash.clone(),
+            transaction_type: tx.transaction_type.clone(),
+            amount: delivered.or_else(|| if partial_payment { None } else { tx.amount().cloned() }),
+            partial_payment,
+            timestamp: tx.datetime(),
+            from: tx.account.clone(),
+            to: tx.destination().map(str::to_string),
+            fee: tx.fee,
+            sequence: tx.sequence,
+            ledger_index: tx.ledger_index,
+        }
+    }
+
+    fn timestamp_rfc3339(&self) -> String {
+        format_rfc3339(self.timestamp.as_ref())
+    }
+}
+
+impl Record for DisplayTransaction {
+    const KIND: &'static str = "transaction";
+
+    fn csv_columns(&self) -> Vec<(&'static str, String)> {
+        let asset = self.amount.as_ref().map(Amount::asset);
+        vec![
+            ("hash", self.hash.clone()),
+            ("transaction_type", self.transaction_type.clone()),
+            ("amount_value", self.amount.as_ref().map(Amount::raw_value).unwrap_or_default()),
+            ("amount_currency", asset.as_ref().map(Asset::code).unwrap_or_default()),
+            ("amount_issuer", asset.as_ref().and_then(Asset::issuer).unwrap_or_default().to_string()),
+            ("partial_payment", self.partial_payment.to_string()),
+            ("timestamp", self.timestamp_rfc3339()),
+            ("from", self.from.clone()),
+            ("to", self.to.clone().unwrap_or_default()),
+            ("fee_drops", self.fee.map(|fee| fee.0.to_string()).unwrap_or_default()),
+            ("sequence", self.sequence.map(|s| s.to_string()).unwrap_or_default()),
+            ("ledger_index", self.ledger_index.map(|l| l.to_string()).unwrap_or_default()),
+        ]
+    }
+}
+
+/// Транзакция с метаданными (подкоманда tx)
+#[derive(Serialize, Debug, Clone)]
+pub struct DisplayTransactionDetails {
+    #[serde(flatten)]
+    pub transaction: DisplayTransaction,
+    pub validated: bool,
+    pub result: Option<String>,
+    pub memos: Vec<DisplayMemo>,
+    pub affected_nodes: Vec<DisplayAffectedNode>,
+}
+
+/// Заметка: текстовые поля декодированы из hex, двоичные оставлены в hex
+#[derive(Serialize, Debug, Clone)]
+pub struct DisplayMemo {
+    pub memo_type: Option<String>,
+    pub memo_data: Option<String>,
+    pub memo_format: Option<String>,
+}
+
+/// Объект леджера, затронутый транзакцией
+#[derive(Serialize, Debug, Clone)]
+pub struct DisplayAffectedNode {
+    /// created, modified или deleted
+    pub change: &'static str,
+    pub ledger_entry_type: String,
+    pub ledger_index: String,
+    /// Поля после транзакции (у созданного объекта - начальные)
+    pub fields: Option<Value>,
+    /// Прежние значения изменившихся полей
+    pub previous_fields: Option<Value>,
+}
+
+impl DisplayTransactionDetails {
+    pub fn from_tx_result(result: &TxResult) -> Self {
+        let meta = result.meta.as_ref();
+        DisplayTransactionDetails {
+            transaction: DisplayTransaction::new(&result.tx, meta),
+            validated: result.validated,
+            result: meta.map(|meta| meta.transaction_result.clone()),
+            memos: result.tx.memos.iter().map(DisplayMemo::from_memo).collect(),
+            affected_nodes: meta
+                .map(|meta| meta.affected_nodes.iter().map(DisplayAffectedNode::from_node).collect())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl DisplayMemo {
+    pub fn from_memo(memo: &Memo) -> Self {
+        let decode = |field: &Option<String>| {
+            field
+                .as_deref()
+                .map(|hex| codec::decode_hex_text(hex).unwrap_or_else(|| hex.to_string()))
+        };
+        DisplayMemo {
+            memo_type: decode(&memo.memo_type),
+            memo_data: decode(&memo.memo_data),
+            memo_format: decode(&memo.memo_format),
+        }
+    }
+}
+
+impl DisplayAffectedNode {
+    pub fn from_node(node: &AffectedNode) -> Self {
+        let ledger_node = node.node();
+        DisplayAffectedNode {
+            change: node.change(),
+            ledger_entry_type: ledger_node.ledger_entry_type.clone(),
+            ledger_index: ledger_node.ledger_index.clone(),
+            fields: ledger_node.new_fields.clone().or_else(|| ledger_node.final_fields.clone()),
+            previous_fields: ledger_node.previous_fields.clone(),
+        }
+    }
+}
+
+impl Record for DisplayTransactionDetails {
+    const KIND: &'static str = "transaction";
+
+    fn csv_columns(&self) -> Vec<(&'static str, String)> {
+        let memos: Vec<String> = self
+            .memos
+            .iter()
+            .map(|memo| memo.memo_data.clone().unwrap_or_default())
+            .collect();
+        let nodes: Vec<String> = self
+            .affected_nodes
+            .iter()
+            .map(|node| format!("{} {} {}", node.change, node.ledger_entry_type, node.ledger_index))
+            .collect();
+
+        let mut columns = self.transaction.csv_columns();
+        columns.extend([
+            ("validated", self.validated.to_string()),
+            ("result", self.result.clone().unwrap_or_default()),
+            ("memos", memos.join("; ")),
+            ("affected_nodes", nodes.join("; ")),
+        ]);
+        columns
+    }
+}
+
+/// Транзакция, затронувшая наблюдаемый адрес (подкоманда watch)
+#[derive(Serialize, Debug, Clone)]
+pub struct DisplayWatchEvent {
+    pub address: String,
+    #[serde(flatten)]
+    pub transaction: DisplayTransaction,
+    pub result: Option<String>,
+    /// Изменение баланса XRP адреса в drops со знаком
+    #[serde(rename = "balance_change_drops")]
+    pub balance_change: Option<String>,
+    #[serde(rename = "balance_drops")]
+    pub balance: Option<Drops>,
+}
+
+impl DisplayWatchEvent {
+    pub fn new(address: &AddressInfo, wrapper: &TransactionWrapper) -> Self {
+        let classic_address = address.classic_address();
+        let change = wrapper
+            .meta
+            .as_ref()
+            .and_then(|meta| meta.xrp_balance_change(&classic_address));
+        DisplayWatchEvent {
+            transaction: wrapper.display(),
+            result: wrapper.meta.as_ref().map(|meta| meta.transaction_result.clone()),
+            balance_change: change.map(|change| change.delta.to_string()),
+            balance: change.map(|change| change.balance),
+            address: classic_address,
+        }
+    }
+}
+
+impl Record for DisplayWatchEvent {
+    const KIND: &'static str = "watch_event";
+
+    fn csv_columns(&self) -> Vec<(&'static str, String)> {
+        let mut columns = vec![("address", self.address.clone())];
+        columns.extend(self.transaction.csv_columns());
+        columns.extend([
+            ("result", self.result.clone().unwrap_or_default()),
+            ("balance_change_drops", self.balance_change.clone().unwrap_or_default()),
+            ("balance_drops", self.balance.map(|b| b.0.to_string()).unwrap_or_default()),
+        ]);
+        columns
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct DisplayBalance {
+    pub address: String,
+    #[serde(rename = "balance_drops")]
+    pub balance: Drops,
+}
+
+impl DisplayBalance {
+    pub fn from_account_data(address: &AddressInfo, account_data: &AccountData) -> Self {
+        DisplayBalance {
+            address: address.classic_address(),
+            balance: account_data.balance,
+        }
+    }
+}
+
+impl Record for DisplayBalance {
+    const KIND: &'static str = "balance";
+
+    fn csv_columns(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("address", self.address.clone()),
+            ("balance_drops", self.balance.0.to_string()),
+            ("balance_xrp", self.balance.to_string()),
+        ]
+    }
+}
+
+/// Сторона книги заявок относительно базового актива
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BookSide {
+    /// Продажа базового актива
+    Ask,
+    /// Покупка базового актива
+    Bid,
+}
+
+/// Уровень книги заявок: цена в котируемом активе за единицу базового
+#[derive(Serialize, Debug, Clone)]
+pub struct DisplayBookLevel {
+    pub side: BookSide,
+    pub price: f64,
+    /// Обеспеченный объем в базовом активе
+    pub size: f64,
+    /// Объем от лучшей цены до этого уровня включительно
+    pub cumulative_size: f64,
+    /// Стоимость уровня в котируемом активе
+    pub total: f64,
+    pub account: String,
+    pub sequence: u32,
+    /// Сводка всей книги, одинаковая для всех уровней
+    #[serde(flatten)]
+    pub summary: BookSummary,
+}
+
+/// Лучшие цены обеих сторон книги и спред между ними
+#[derive(Serialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct BookSummary {
+    pub best_ask: Option<f64>,
+    pub best_bid: Option<f64>,
+    /// best_ask - best_bid; только если есть обе стороны
+    pub spread: Option<f64>,
+}
+
+impl BookSummary {
+    /// Сводка по уровням, упорядоченным от лучшей цены на каждой стороне
+    pub fn from_levels(levels: &[DisplayBookLevel]) -> Self {
+        let best = |side: BookSide| levels.iter().find(|level| level.side == side).map(|level| level.price);
+        let best_ask = best(BookSide::Ask);
+        let best_bid = best(BookSide::Bid);
+        BookSummary {
+            best_ask,
+            best_bid,
+            spread: best_ask.zip(best_bid).map(|(ask, bid)| ask - bid),
+        }
+    }
+
+    /// Спред в процентах от средней цены
+    pub fn spread_percent(&self) -> Option<f64> {
+        let mid = (self.best_ask? + self.best_bid?) / 2.0;
+        Some(self.spread? / mid * 100.0)
+    }
+}
+
+impl DisplayBookLevel {
+    /// Обе стороны книги: asks, затем bids. Каждый уровень несет сводку всей книги,
+    /// чтобы лучшие цены и спред были и в машиночитаемом выводе
+    pub fn order_book(asks: &[BookOffer], bids: &[BookOffer]) -> Vec<Self> {
+        let mut levels = Self::levels(BookSide::Ask, asks);
+        levels.extend(Self::levels(BookSide::Bid, bids));
+
+        let summary = BookSummary::from_levels(&levels);
+        for level in &mut levels {
+            level.summary = summary;
+        }
+        levels
+    }
+
+
+    /// Уровни одной стороны в порядке book_offers (от лучшей цены).
+    /// Для asks предложения отдают базовый актив, для bids - получают его.
+    /// Полностью необеспеченные предложения пропускаются.
+    pub fn levels(side: BookSide, offers: &[BookOffer]) -> Vec<Self> {
+        let mut cumulative_size = 0.0;
+        offers
+            .iter()
+            .filter_map(|offer| {
+                let (price, size) = match side {
+                    BookSide::Ask => (amount::price(&offer.taker_gets, &offer.taker_pays)?, offer.funded_gets().to_f64()?),
+                    BookSide::Bid => (amount::price(&offer.taker_pays, &offer.taker_gets)?, offer.funded_pays().to_f64()?),
+                };
+                if size <= 0.0 {
+                    return None;
+                }
+                cumulative_size += size;
+                Some(DisplayBookLevel {
+                    side,
+                    price,
+                    size,
+                    cumulative_size,
+                    total: size * price,
+                    account: offer.account.clone(),
+                    sequence: offer.sequence,
+                    summary: BookSummary::default(),
+                })
             })
-        } else {
-            None
+            .collect()
+    }
+}
+
+impl Record for DisplayBookLevel {
+    const KIND: &'static str = "book_level";
+
+    fn csv_columns(&self) -> Vec<(&'static str, String)> {
+        let side = match self.side {
+            BookSide::Ask => "ask",
+            BookSide::Bid => "bid",
+        };
+        vec![
+            ("side", side.to_string()),
+            ("price", self.price.to_string()),
+            ("size", self.size.to_string()),
+            ("cumulative_size", self.cumulative_size.to_string()),
+            ("total", self.total.to_string()),
+            ("account", self.account.clone()),
+            ("sequence", self.sequence.to_string()),
+            ("best_ask", format_optional(self.summary.best_ask)),
+            ("best_bid", format_optional(self.summary.best_bid)),
+            ("spread", format_optional(self.summary.spread)),
+        ]
+    }
+}
+
+/// Результат проверки адреса без обращения к сети
+#[derive(Serialize, Debug, Clone)]
+pub struct DisplayAddress {
+    pub input: String,
+    pub valid: bool,
+    pub classic_address: Option<String>,
+    pub x_address: Option<String>,
+    pub account_id: Option<String>,
+    pub tag: Option<u32>,
+    pub network: Option<String>,
+    pub error: Option<String>,
+}
+
+impl DisplayAddress {
+    pub fn valid(input: &str, address: &AddressInfo, network: Network) -> Self {
+        DisplayAddress {
+            input: input.to_string(),
+            valid: true,
+            classic_address: Some(address.classic_address()),
+            x_address: address.x_address(network),
+            account_id: Some(address.hex()),
+            tag: address.tag,
+            network: address.network.map(|network| network.to_string()),
+            error: None,
         }
     }
-}
\ No newline at end of file
+
+    pub fn invalid(input: &str, error: String) -> Self {
+        DisplayAddress {
+            input: input.to_string(),
+            valid: false,
+            classic_address: None,
+            x_address: None,
+            account_id: None,
+            tag: None,
+            network: None,
+            error: Some(error),
+        }
+    }
+}
+
+impl Record for DisplayAddress {
+    const KIND: &'static str = "address";
+
+    fn csv_columns(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("input", self.input.clone()),
+            ("valid", self.valid.to_string()),
+            ("classic_address", self.classic_address.clone().unwrap_or_default()),
+            ("x_address", self.x_address.clone().unwrap_or_default()),
+            ("account_id", self.account_id.clone().unwrap_or_default()),
+            ("tag", self.tag.map(|t| t.to_string()).unwrap_or_default()),
+            ("network", self.network.clone().unwrap_or_default()),
+            ("error", self.error.clone().unwrap_or_default()),
+        ]
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct DisplayTrustLine {
+    pub currency: String,
+    pub issuer: String,
+    pub balance: String,
+    pub limit: String,
+    pub limit_peer: String,
+    pub no_ripple: bool,
+    pub no_ripple_peer: bool,
+    pub authorized: bool,
+    pub peer_authorized: bool,
+    pub freeze: bool,
+    pub freeze_peer: bool,
+    pub quality_in: u32,
+    pub quality_out: u32,
+}
+
+impl DisplayTrustLine {
+    pub fn from_trust_line(line: &TrustLine) -> Self {
+        DisplayTrustLine {
+            currency: line.currency.display_name(),
+            issuer: line.account.clone(),
+            balance: line.balance.clone(),
+            limit: line.limit.clone(),
+            limit_peer: line.limit_peer.clone(),
+            no_ripple: line.no_ripple,
+            no_ripple_peer: line.no_ripple_peer,
+            authorized: line.authorized,
+            peer_authorized: line.peer_authorized,
+            freeze: line.freeze,
+            freeze_peer: line.freeze_peer,
+            quality_in: line.quality_in,
+            quality_out: line.quality_out,
+        }
+    }
+}
+
+impl Record for DisplayTrustLine {
+    const KIND: &'static str = "trust_line";
+
+    fn csv_columns(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("currency", self.currency.clone()),
+            ("issuer", self.issuer.clone()),
+            ("balance", self.balance.clone()),
+            ("limit", self.limit.clone()),
+            ("limit_peer", self.limit_peer.clone()),
+            ("no_ripple", self.no_ripple.to_string()),
+            ("no_ripple_peer", self.no_ripple_peer.to_string()),
+            ("authorized", self.authorized.to_string()),
+            ("peer_authorized", self.peer_authorized.to_string()),
+            ("freeze", self.freeze.to_string()),
+            ("freeze_peer", self.freeze_peer.to_string()),
+            ("quality_in", self.quality_in.to_string()),
+            ("quality_out", self.quality_out.to_string()),
+        ]
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct DisplayOffer {
+    pub sequence: u32,
+    pub taker_gets: Amount,
+    pub taker_pays: Amount,
+    pub quality: String,
+    /// Цена в единицах taker_pays за единицу taker_gets
+    pub price: Option<f64>,
+    pub expiration: Option<DateTime<Utc>>,
+    pub flags: u32,
+    pub passive: bool,
+    pub sell: bool,
+}
+
+impl DisplayOffer {
+    pub fn from_offer(offer: &AccountOffer) -> Self {
+        DisplayOffer {
+            sequence: offer.seq,
+            taker_gets: offer.taker_gets.clone(),
+            taker_pays: offer.taker_pays.clone(),
+            quality: offer.quality.clone(),
+            price: offer.price(),
+            expiration: offer.expiration(),
+            flags: offer.flags,
+            passive: offer.is_passive(),
+            sell: offer.is_sell(),
+        }
+    }
+}
+
+impl Record for DisplayOffer {
+    const KIND: &'static str = "offer";
+
+    fn csv_columns(&self) -> Vec<(&'static str, String)> {
+        let gets = self.taker_gets.asset();
+        let pays = self.taker_pays.asset();
+        vec![
+            ("sequence", self.sequence.to_string()),
+            ("taker_gets_value", self.taker_gets.raw_value()),
+            ("taker_gets_currency", gets.code()),
+            ("taker_gets_issuer", gets.issuer().unwrap_or_default().to_string()),
+            ("taker_pays_value", self.taker_pays.raw_value()),
+            ("taker_pays_currency", pays.code()),
+            ("taker_pays_issuer", pays.issuer().unwrap_or_default().to_string()),
+            ("quality", self.quality.clone()),
+            ("price", self.price.map(|p| p.to_string()).unwrap_or_default()),
+            ("expiration", format_rfc3339(self.expiration.as_ref())),
+            ("flags", self.flags.to_string()),
+            ("passive", self.passive.to_string()),
+            ("sell", self.sell.to_string()),
+        ]
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct DisplayNft {
+    pub nftoken_id: String,
+    pub issuer: String,
+    pub taxon: u32,
+    pub serial: u32,
+    pub transfer_fee: Option<u16>,
+    pub flags: u32,
+    /// URI в исходном hex-виде
+    pub uri: Option<String>,
+    /// URI, декодированный в текст (если это текст)
+    pub uri_text: Option<String>,
+}
+
+impl DisplayNft {
+    pub fn from_nftoken(nft: &NfToken) -> Self {
+        DisplayNft {
+            nftoken_id: nft.nftoken_id.clone(),
+            issuer: nft.issuer.clone(),
+            taxon: nft.nftoken_taxon,
+            serial: nft.nft_serial,
+            transfer_fee: nft.transfer_fee,
+            flags: nft.flags,
+            uri: nft.uri.clone(),
+            uri_text: nft.uri.as_deref().and_then(codec::decode_hex_text),
+        }
+    }
+}
+
+impl Record for DisplayNft {
+    const KIND: &'static str = "nft";
+
+    fn csv_columns(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("nftoken_id", self.nftoken_id.clone()),
+            ("issuer", self.issuer.clone()),
+            ("taxon", self.taxon.to_string()),
+            ("serial", self.serial.to_string()),
+            ("transfer_fee", self.transfer_fee.map(|f| f.to_string()).unwrap_or_default()),
+            ("flags", self.flags.to_string()),
+            ("uri", self.uri.clone().unwrap_or_default()),
+            ("uri_text", self.uri_text.clone().unwrap_or_default()),
+        ]
+    }
+}
+
+/// Объект леджера, принадлежащий аккаунту
+#[derive(Serialize, Debug, Clone)]
+pub struct DisplayLedgerObject {
+    pub index: String,
+    pub ledger_entry_type: String,
+    pub object: Value,
+}
+
+impl DisplayLedgerObject {
+    pub fn from_value(object: &Value) -> Self {
+        let field = |name: &str| object.get(name).and_then(Value::as_str).unwrap_or_default().to_string();
+        DisplayLedgerObject {
+            index: field("index"),
+            ledger_entry_type: field("LedgerEntryType"),
+            object: object.clone(),
+        }
+    }
+}
+
+impl Record for DisplayLedgerObject {
+    const KIND: &'static str = "ledger_object";
+
+    fn csv_columns(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("index", self.index.clone()),
+            ("ledger_entry_type", self.ledger_entry_type.clone()),
+            ("object", self.object.to_string()),
+        ]
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct DisplayLedger {
+    pub ledger_index: Option<u32>,
+    pub ledger_hash: Option<String>,
+    pub parent_hash: String,
+    pub close_time: Option<DateTime<Utc>>,
+    #[serde(rename = "total_coins_drops")]
+    pub total_coins: Drops,
+    pub closed: bool,
+    pub validated: bool,
+}
+
+impl DisplayLedger {
+    pub fn from_ledger_result(result: &LedgerResult) -> Self {
+        DisplayLedger {
+            ledger_index: result.ledger_index.or(result.ledger_current_index),
+            ledger_hash: result.ledger_hash.clone(),
+            parent_hash: result.ledger.parent_hash.clone(),
+            close_time: result.ledger.close_time.and_then(time::ripple_to_datetime),
+            total_coins: result.ledger.total_coins,
+            closed: result.ledger.closed,
+            validated: result.validated,
+        }
+    }
+}
+
+impl Record for DisplayLedger {
+    const KIND: &'static str = "ledger";
+
+    fn csv_columns(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("ledger_index", self.ledger_index.map(|i| i.to_string()).unwrap_or_default()),
+            ("ledger_hash", self.ledger_hash.clone().unwrap_or_default()),
+            ("parent_hash", self.parent_hash.clone()),
+            ("close_time", format_rfc3339(self.close_time.as_ref())),
+            ("total_coins_drops", self.total_coins.0.to_string()),
+            ("closed", self.closed.to_string()),
+            ("validated", self.validated.to_string()),
+        ]
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct DisplayServerInfo {
+    pub build_version: String,
+    pub server_state: String,
+    pub complete_ledgers: String,
+    pub network_id: Option<u32>,
+    pub peers: Option<u32>,
+    pub uptime: Option<u64>,
+    pub load_factor: Option<f64>,
+    pub validated_ledger: Option<ValidatedLedger>,
+}
+
+impl DisplayServerInfo {
+    pub fn from_server_info(info: &ServerInfo) -> Self {
+        DisplayServerInfo {
+            build_version: info.build_version.clone(),
+            server_state: info.server_state.clone(),
+            complete_ledgers: info.complete_ledgers.clone(),
+            network_id: info.network_id,
+            peers: info.peers,
+            uptime: info.uptime,
+            load_factor: info.load_factor,
+            validated_ledger: info.validated_ledger.clone(),
+        }
+    }
+}
+
+impl Record for DisplayServerInfo {
+    const KIND: &'static str = "server";
+
+    fn csv_columns(&self) -> Vec<(&'static str, String)> {
+        let ledger = self.validated_ledger.as_ref();
+        vec![
+            ("build_version", self.build_version.clone()),
+            ("server_state", self.server_state.clone()),
+            ("complete_ledgers", self.complete_ledgers.clone()),
+            ("network_id", self.network_id.map(|n| n.to_string()).unwrap_or_default()),
+            ("peers", self.peers.map(|p| p.to_string()).unwrap_or_default()),
+            ("uptime", self.uptime.map(|u| u.to_string()).unwrap_or_default()),
+            ("load_factor", self.load_factor.map(|l| l.to_string()).unwrap_or_default()),
+            ("validated_ledger_seq", ledger.map(|l| l.seq.to_string()).unwrap_or_default()),
+            ("validated_ledger_age", ledger.map(|l| l.age.to_string()).unwrap_or_default()),
+            ("base_fee_xrp", ledger.map(|l| l.base_fee_xrp.to_string()).unwrap_or_default()),
+            ("reserve_base_xrp", ledger.map(|l| l.reserve_base_xrp.to_string()).unwrap_or_default()),
+            ("reserve_inc_xrp", ledger.map(|l| l.reserve_inc_xrp.to_string()).unwrap_or_default()),
+        ]
+    }
+}
+
+fn format_rfc3339(datetime: Option<&DateTime<Utc>>) -> String {
+    datetime
+        .map(|t| t.to_rfc3339_opts(SecondsFormat::Secs, true))
+        .unwrap_or_default()
+}
+
+fn format_optional(value: Option<f64>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+/// Десятичная строка ("0", "-1.5", "1e-3") отлична от нуля
+fn is_nonzero(value: &str) -> bool {
+    let mantissa = value.split(['e', 'E']).next().unwrap_or_default();
+    mantissa.chars().any(|c| c.is_ascii_digit() && c != '0')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::address::AddressValidator;
+    use crate::test_fixtures::{self, ACCOUNT};
+    use crate::transaction::TF_PARTIAL_PAYMENT;
+    use serde_json::json;
+
+    fn payment(flags: u32) -> Transaction {
+        serde_json::from_value(test_fixtures::payment(json!("1000000"), flags)).unwrap()
+    }
+
+    fn meta(delivered_amount: Option<&str>) -> TransactionMeta {
+        let mut meta = test_fixtures::meta(json!([]));
+        if let Some(delivered_amount) = delivered_amount {
+            meta["delivered_amount"] = json!(delivered_amount);
+        }
+        serde_json::from_value(meta).unwrap()
+    }
+
+    #[test]
+    fn payment_amount_falls_back_to_amount_field() {
+        let tx = DisplayTransaction::new(&payment(0), None);
+        assert_eq!(tx.amount, Some(Amount::Xrp(Drops(1_000_000))));
+        assert!(!tx.partial_payment);
+    }
+
+    #[test]
+    fn payment_amount_prefers_delivered_amount() {
+        let tx = DisplayTransaction::new(&payment(TF_PARTIAL_PAYMENT), Some(&meta(Some("250000"))));
+        assert_eq!(tx.amount, Some(Amount::Xrp(Drops(250_000))));
+        assert!(tx.partial_payment);
+    }
+
+    #[test]
+    fn partial_payment_without_delivered_amount_is_unknown() {
+        let tx = DisplayTransaction::new(&payment(TF_PARTIAL_PAYMENT), None);
+        assert_eq!(tx.amount, None);
+        // Транзакции до 2014 года: delivered_amount = "unavailable"
+        let tx = DisplayTransaction::new(&payment(TF_PARTIAL_PAYMENT), Some(&meta(Some("unavailable"))));
+        assert_eq!(tx.amount, None);
+    }
+
+    /// Сводка по AccountRoot с балансом 25 XRP; `fields` дополняют или заменяют поля
+    fn account_info(fields: Value) -> DisplayAccountInfo {
+        let mut data = json!({ "Account": ACCOUNT, "Balance": "25000000", "Sequence": 42 });
+        data.as_object_mut().unwrap().extend(fields.as_object().unwrap().clone());
+        let data: AccountData = serde_json::from_value(data).unwrap();
+        let address = AddressValidator::new().parse(ACCOUNT).unwrap();
+        DisplayAccountInfo::from_account_data(&address, Network::Mainnet, &data)
+    }
+
+    const RESERVES: Reserves = Reserves { base: Drops(10_000_000), owner: Drops(2_000_000) };
+
+    #[test]
+    fn reserve_grows_with_owner_count() {
+        assert_eq!(RESERVES.for_owner_count(0), Ok(Drops(10_000_000)));
+        assert_eq!(RESERVES.for_owner_count(3), Ok(Drops(16_000_000)));
+
+        let huge = Reserves { base: Drops(1), owner: Drops(u64::MAX) };
+        assert_eq!(huge.for_owner_count(2), Err(AmountError::Overflow));
+        assert_eq!(huge.for_owner_count(0), Ok(Drops(1)));
+    }
+
+    #[test]
+    fn available_balance_excludes_reserve() {
+        let info = account_info(json!({ "OwnerCount": 3 })).with_reserves(Some(&RESERVES)).unwrap();
+        assert_eq!(info.reserved, Some(Drops(16_000_000)));
+        assert_eq!(info.available, Some(Drops(9_000_000)));
+
+        let info = account_info(json!({ "OwnerCount": 3 })).with_reserves(None).unwrap();
+        assert_eq!(info.reserved, None);
+        assert_eq!(info.available, None);
+    }
+
+    #[test]
+    fn available_balance_saturates_below_reserve() {
+        // Резервы сети выросли после того, как аккаунт создал объекты
+        let info = account_info(json!({ "Balance": "12000000", "OwnerCount": 3 }))
+            .with_reserves(Some(&RESERVES))
+            .unwrap();
+        assert_eq!(info.reserved, Some(Drops(16_000_000)));
+        assert_eq!(info.available, Some(Drops::ZERO));
+    }
+
+    #[test]
+    fn decodes_account_flag_bits() {
+        // lsfRequireDestTag | lsfDisableMaster | lsfDefaultRipple | lsfAMM | lsfAllowTrustLineClawback
+        let flags = AccountFlags::from_bits(0x0002_0000 | 0x0010_0000 | 0x0080_0000 | 0x0200_0000 | 0x8000_0000);
+        assert_eq!(
+            flags,
+            AccountFlags {
+                require_dest_tag: true,
+                disable_master: true,
+                default_ripple: true,
+                amm: true,
+                allow_trust_line_clawback: true,
+                ..AccountFlags::default()
+            }
+        );
+        assert_eq!(
+            flags.names(),
+            [
+                "lsfRequireDestTag",
+                "lsfDisableMaster",
+                "lsfDefaultRipple",
+                "lsfAMM",
+                "lsfAllowTrustLineClawback"
+            ]
+        );
+        assert!(AccountFlags::from_bits(0).names().is_empty());
+    }
+
+    #[test]
+    fn decodes_domain_from_hex() {
+        let info = account_info(json!({ "Domain": "6578616D706C652E636F6D", "Flags": 0x0080_0000 }));
+        assert_eq!(info.domain.as_deref(), Some("example.com"));
+        assert!(info.account_flags.default_ripple);
+
+        // Нечитаемый Domain выводится как есть
+        assert_eq!(account_info(json!({ "Domain": "0001" })).domain.as_deref(), Some("0001"));
+        assert_eq!(account_info(json!({ "Domain": "not hex" })).domain.as_deref(), Some("not hex"));
+        assert_eq!(account_info(json!({})).domain, None);
+    }
+
+    #[test]
+    fn account_pages_are_pinned_to_first_page_ledger() {
+        let page: AccountLinesResult =
+            serde_json::from_value(json!({ "lines": [], "marker": "A", "ledger_current_index": 95 })).unwrap();
+        assert_eq!(page.ledger.index(), Some(95));
+
+        let request = AccountPageRequest::new("account_lines", ACCOUNT.to_string(), Some(400), page.marker)
+            .at_ledger(page.ledger.index());
+        let value = serde_json::to_value(&request).unwrap();
+        assert_eq!(value["params"][0]["ledger_index"], 95);
+        assert_eq!(value["params"][0]["marker"], "A");
+
+        let first = AccountPageRequest::new("account_lines", ACCOUNT.to_string(), Some(400), None);
+        let value = serde_json::to_value(&first).unwrap();
+        assert!(value["params"][0].get("ledger_index").is_none());
+    }
+
+    const USD_ISSUER: &str = "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B";
+
+    fn usd(value: &str) -> Value {
+        json!({ "currency": "USD", "issuer": USD_ISSUER, "value": value })
+    }
+
+    fn book_offer(sequence: u32, gets: Value, pays: Value, gets_funded: Option<Value>) -> BookOffer {
+        let mut offer = json!({
+            "Account": ACCOUNT,
+            "Sequence": sequence,
+            "TakerGets": gets,
+            "TakerPays": pays,
+        });
+        if let Some(funded) = gets_funded {
+            offer["taker_gets_funded"] = funded;
+        }
+        serde_json::from_value(offer).unwrap()
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn ask_levels_use_funded_size_and_accumulate() {
+        // Продажа XRP за USD: цена в USD за XRP
+        let offers = [
+            book_offer(1, json!("10000000"), usd("5"), None),
+            book_offer(2, json!("20000000"), usd("12"), Some(json!("5000000"))),
+        ];
+        let levels = DisplayBookLevel::levels(BookSide::Ask, &offers);
+        assert_eq!(levels.len(), 2);
+        assert_close(levels[0].price, 0.5);
+        assert_close(levels[0].size, 10.0);
+        assert_close(levels[0].total, 5.0);
+        assert_close(levels[1].price, 0.6);
+        assert_close(levels[1].size, 5.0);
+        assert_close(levels[1].cumulative_size, 15.0);
+        assert_close(levels[1].total, 3.0);
+        assert_eq!(levels[1].sequence, 2);
+    }
+
+    #[test]
+    fn bid_levels_price_base_asset_received() {
+        // Покупка XRP за USD: предложение отдает USD и получает XRP
+        let offers = [book_offer(3, usd("4.5"), json!("10000000"), None)];
+        let levels = DisplayBookLevel::levels(BookSide::Bid, &offers);
+        assert_eq!(levels.len(), 1);
+        assert_eq!(levels[0].side, BookSide::Bid);
+        assert_close(levels[0].price, 0.45);
+        assert_close(levels[0].size, 10.0);
+        assert_close(levels[0].total, 4.5);
+    }
+
+    #[test]
+    fn skips_unfunded_offers() {
+        let offers = [
+            book_offer(1, json!("1000000"), usd("1"), Some(json!("0"))),
+            book_offer(2, json!("2000000"), usd("1"), None),
+        ];
+        let levels = DisplayBookLevel::levels(BookSide::Ask, &offers);
+        assert_eq!(levels.len(), 1);
+        assert_eq!(levels[0].sequence, 2);
+        assert_close(levels[0].cumulative_size, 2.0);
+    }
+
+    #[test]
+    fn order_book_summary_is_in_every_level() {
+        let asks = [
+            book_offer(1, json!("10000000"), usd("5"), None),
+            book_offer(2, json!("10000000"), usd("6"), None),
+        ];
+        let bids = [book_offer(3, usd("4.5"), json!("10000000"), None)];
+        let levels = DisplayBookLevel::order_book(&asks, &bids);
+        assert_eq!(levels.len(), 3);
+
+        let summary = levels[0].summary;
+        assert!(levels.iter().all(|level| level.summary == summary));
+        assert_close(summary.best_ask.unwrap(), 0.5);
+        assert_close(summary.best_bid.unwrap(), 0.45);
+        assert_close(summary.spread.unwrap(), 0.05);
+        assert_close(summary.spread_percent().unwrap(), 0.05 / 0.475 * 100.0);
+
+        let record = serde_json::to_value(&levels[2]).unwrap();
+        assert_eq!(record["side"], "bid");
+        assert_close(record["best_ask"].as_f64().unwrap(), 0.5);
+        let columns = levels[2].csv_columns();
+        assert!(columns.iter().any(|(name, value)| *name == "spread" && !value.is_empty()));
+    }
+
+    #[test]
+    fn one_sided_book_has_no_spread() {
+        let asks = [book_offer(1, json!("10000000"), usd("5"), None)];
+        let levels = DisplayBookLevel::order_book(&asks, &[]);
+        let summary = levels[0].summary;
+        assert_close(summary.best_ask.unwrap(), 0.5);
+        assert_eq!(summary.best_bid, None);
+        assert_eq!(summary.spread, None);
+        assert_eq!(summary.spread_percent(), None);
+        assert_eq!(BookSummary::from_levels(&[]), BookSummary::default());
+    }
+}
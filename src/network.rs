@@ -0,0 +1,155 @@
+// network.rs - выбор сети XRPL и адреса сервера rippled
+use std::fmt;
+
+use clap::ValueEnum;
+use thiserror::Error;
+
+use crate::address::{AddressInfo, AddressNetwork};
+
+/// Ошибки конфигурации сети
+#[derive(Error, Debug)]
+pub enum NetworkError {
+    #[error("Для сети custom необходимо указать --url или XRP_VIEWER_URL")]
+    MissingUrl,
+
+    #[error("Адрес предназначен для сети {address}, а выбрана сеть {network}")]
+    Mismatch { address: &'static str, network: Network },
+}
+
+/// Предустановленные сети XRPL
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Network {
+    /// Основная сеть
+    Mainnet,
+    /// Тестовая сеть (altnet)
+    Testnet,
+    /// Сеть разработчиков
+    Devnet,
+    /// Собственный сервер (адрес задается через --url)
+    Custom,
+}
+
+impl Network {
+    /// Сеть X-адресов: тестовые сети (testnet, devnet) используют префикс `T`
+    pub fn address_network(&self) -> Option<AddressNetwork> {
+        match self {
+            Network::Mainnet => Some(AddressNetwork::Mainnet),
+            Network::Testnet | Network::Devnet => Some(AddressNetwork::Testnet),
+            Network::Custom => None,
+        }
+    }
+
+    /// Публичные JSON-RPC серверы сети по умолчанию (в порядке предпочтения)
+    pub fn default_urls(&self) -> &'static [&'static str] {
+        match self {
+            Network::Mainnet => &[
+                "https://s1.ripple.com:51234/",
+                "https://s2.ripple.com:51234/",
+                "https://xrplcluster.com/",
+            ],
+            Network::Testnet => &[
+                "https://s.altnet.rippletest.net:51234/",
+                "https://testnet.xrpl-labs.com/",
+            ],
+            Network::Devnet => &["https://s.devnet.rippletest.net:51234/"],
+            Network::Custom => &[],
+        }
+    }
+
+    /// Публичные WebSocket-серверы сети для подписок (в порядке предпочтения)
+    pub fn default_ws_urls(&self) -> &'static [&'static str] {
+        match self {
+            Network::Mainnet => &[
+                "wss://s1.ripple.com/",
+                "wss://s2.ripple.com/",
+                "wss://xrplcluster.com/",
+            ],
+            Network::Testnet => &[
+                "wss://s.altnet.rippletest.net:51233/",
+                "wss://testnet.xrpl-labs.com/",
+            ],
+            Network::Devnet => &["wss://s.devnet.rippletest.net:51233/"],
+            Network::Custom => &[],
+        }
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Network::Mainnet => "mainnet",
+            Network::Testnet => "testnet",
+            Network::Devnet => "devnet",
+            Network::Custom => "custom",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Итоговая конфигурация подключения
+#[derive(Debug, Clone)]
+pub struct NetworkConfig {
+    pub network: Network,
+    /// Серверы rippled в порядке предпочтения (не пустой список)
+    pub urls: Vec<String>,
+    /// WebSocket-серверы для подписок (пусто, если явно заданы только HTTP-серверы)
+    pub ws_urls: Vec<String>,
+}
+
+impl NetworkConfig {
+    /// Определяет сеть и серверы по аргументам командной строки.
+    /// Явные URL имеют приоритет над серверами сети; без указания сети они считаются custom.
+    pub fn resolve(network: Option<Network>, urls: Vec<String>) -> Result<Self, NetworkError> {
+        let urls: Vec<String> = urls
+            .iter()
+            .map(|u| u.trim().to_string())
+            .filter(|u| !u.is_empty())
+            .collect();
+
+        if !urls.is_empty() {
+            let ws_urls = urls
+                .iter()
+                .filter(|u| u.starts_with("ws://") || u.starts_with("wss://"))
+                .cloned()
+                .collect();
+            return Ok(NetworkConfig {
+                network: network.unwrap_or(Network::Custom),
+                urls,
+                ws_urls,
+            });
+        }
+
+        let network = network.unwrap_or(Network::Mainnet);
+        let urls = network.default_urls();
+        if urls.is_empty() {
+            return Err(NetworkError::MissingUrl);
+        }
+        Ok(NetworkConfig {
+            network,
+            urls: urls.iter().map(|u| u.to_string()).collect(),
+            ws_urls: network.default_ws_urls().iter().map(|u| u.to_string()).collect(),
+        })
+    }
+
+    /// Проверяет, что X-адрес предназначен для выбранной сети.
+    /// Классические адреса и custom-сеть не проверяются.
+    pub fn check_address(&self, address: &AddressInfo) -> Result<(), NetworkError> {
+        let compatible = match (address.network, self.network) {
+            (None, _) | (_, Network::Custom) => true,
+            (Some(AddressNetwork::Mainnet), network) => network == Network::Mainnet,
+            (Some(AddressNetwork::Testnet), network) => network != Network::Mainnet,
+        };
+
+        if compatible {
+            Ok(())
+        } else {
+            Err(NetworkError::Mismatch {
+                address: match address.network {
+                    Some(AddressNetwork::Mainnet) => "mainnet",
+                    _ => "testnet",
+                },
+                network: self.network,
+            })
+        }
+    }
+}
@@ -0,0 +1,317 @@
+// output.rs - машиночитаемый вывод: JSON, NDJSON, CSV, YAML
+use std::io::{self, Write};
+
+use clap::ValueEnum;
+use serde::{ser::Error as _, Serialize};
+use serde_json::Value;
+
+use crate::error::ViewerError;
+
+/// Результат операций вывода
+pub type Result<T> = std::result::Result<T, ViewerError>;
+
+/// Версия схемы машиночитаемого вывода.
+/// Увеличивается при любом несовместимом изменении полей записей.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Формат вывода результатов
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Текст для человека
+    #[default]
+    Text,
+    /// JSON (объект или массив записей)
+    Json,
+    /// JSON по одной записи на строку
+    Ndjson,
+    /// CSV с заголовком
+    Csv,
+    /// YAML
+    Yaml,
+}
+
+impl OutputFormat {
+    /// Создает приемник записей; для текстового формата - None
+    /// (текст выводится через `DisplayFormatter`).
+    /// `list` определяет, выводится ли список записей или одна запись.
+    pub fn writer(self, list: bool) -> Option<Box<dyn OutputWriter>> {
+        self.writer_to(list, Box::new(io::stdout()))
+    }
+
+    /// То же, что `writer`, но с выводом в `out` вместо stdout
+    pub fn writer_to(self, list: bool, out: Box<dyn Write>) -> Option<Box<dyn OutputWriter>> {
+        match self {
+            OutputFormat::Text => None,
+            OutputFormat::Json => Some(Box::new(JsonWriter::new(list, out))),
+            OutputFormat::Ndjson => Some(Box::new(NdjsonWriter { out })),
+            OutputFormat::Csv => Some(Box::new(CsvWriter::new(out))),
+            OutputFormat::Yaml => Some(Box::new(YamlWriter::new(list, out))),
+        }
+    }
+}
+
+/// Запись машиночитаемого вывода
+pub trait Record: Serialize {
+    /// Тип записи в схеме (поле `kind`)
+    const KIND: &'static str;
+
+    /// Колонки CSV: имя и значение
+    fn csv_columns(&self) -> Vec<(&'static str, String)>;
+}
+
+/// Запись, подготовленная к выводу в любом формате
+pub struct Entry {
+    value: Value,
+    columns: Vec<(&'static str, String)>,
+}
+
+impl Entry {
+    pub fn new<R: Record>(record: &R) -> Result<Self> {
+        let mut value = serde_json::to_value(record).map_err(ViewerError::Json)?;
+        let fields = value.as_object_mut().ok_or_else(|| {
+            ViewerError::Json(serde_json::Error::custom("запись вывода должна сериализоваться в объект"))
+        })?;
+
+        // Версия схемы и тип записи всегда идут первыми
+        let mut envelope = serde_json::Map::new();
+        envelope.insert("schema_version".to_string(), SCHEMA_VERSION.into());
+        envelope.insert("kind".to_string(), R::KIND.into());
+        envelope.append(fields);
+
+        let mut columns = vec![
+            ("schema_version", SCHEMA_VERSION.to_string()),
+            ("kind", R::KIND.to_string()),
+        ];
+        columns.extend(record.csv_columns());
+
+        Ok(Entry {
+            value: Value::Object(envelope),
+            columns,
+        })
+    }
+}
+
+/// Приемник записей в одном из машиночитаемых форматов
+pub trait OutputWriter {
+    /// Выводит (или накапливает) запись
+    fn write(&mut self, entry: Entry) -> Result<()>;
+
+    /// Завершает вывод (для форматов, которым нужен весь список целиком)
+    fn finish(&mut self) -> Result<()>;
+}
+
+/// Выводит одну запись через приемник
+pub fn write_record<R: Record>(writer: &mut dyn OutputWriter, record: &R) -> Result<()> {
+    writer.write(Entry::new(record)?)
+}
+
+struct JsonWriter {
+    list: bool,
+    items: Vec<Value>,
+    out: Box<dyn Write>,
+}
+
+impl JsonWriter {
+    fn new(list: bool, out: Box<dyn Write>) -> Self {
+        JsonWriter { list, items: Vec::new(), out }
+    }
+}
+
+impl OutputWriter for JsonWriter {
+    fn write(&mut self, entry: Entry) -> Result<()> {
+        self.items.push(entry.value);
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        let items = std::mem::take(&mut self.items);
+        let value = if self.list {
+            Value::Array(items)
+        } else {
+            items.into_iter().next().unwrap_or(Value::Null)
+        };
+        writeln!(self.out, "{}", serde_json::to_string_pretty(&value).map_err(ViewerError::Json)?)?;
+        Ok(())
+    }
+}
+
+struct NdjsonWriter {
+    out: Box<dyn Write>,
+}
+
+impl OutputWriter for NdjsonWriter {
+    fn write(&mut self, entry: Entry) -> Result<()> {
+        serde_json::to_writer(&mut self.out, &entry.value).map_err(ViewerError::Json)?;
+        writeln!(self.out)?;
+        self.out.flush()?;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+struct CsvWriter {
+    writer: csv::Writer<Box<dyn Write>>,
+    header_written: bool,
+}
+
+impl CsvWriter {
+    fn new(out: Box<dyn Write>) -> Self {
+        CsvWriter {
+            writer: csv::Writer::from_writer(out),
+            header_written: false,
+        }
+    }
+}
+
+impl OutputWriter for CsvWriter {
+    fn write(&mut self, entry: Entry) -> Result<()> {
+        if !self.header_written {
+            self.writer.write_record(entry.columns.iter().map(|(name, _)| *name))?;
+            self.header_written = true;
+        }
+        self.writer.write_record(entry.columns.iter().map(|(_, value)| value))?;
+        // Строки выводятся сразу, чтобы поток (history, watch) можно было читать по мере поступления
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+struct YamlWriter {
+    list: bool,
+    items: Vec<Value>,
+    out: Box<dyn Write>,
+}
+
+impl YamlWriter {
+    fn new(list: bool, out: Box<dyn Write>) -> Self {
+        YamlWriter { list, items: Vec::new(), out }
+    }
+}
+
+impl OutputWriter for YamlWriter {
+    fn write(&mut self, entry: Entry) -> Result<()> {
+        self.items.push(entry.value);
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        let items = std::mem::take(&mut self.items);
+        let yaml = if self.list {
+            serde_yaml::to_string(&items)?
+        } else {
+            serde_yaml::to_string(&items.into_iter().next().unwrap_or(Value::Null))?
+        };
+        write!(self.out, "{}", yaml)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// Буфер вывода, доступный тесту после передачи приемнику
+    #[derive(Clone, Default)]
+    struct Buffer(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[derive(Serialize)]
+    struct Sample {
+        name: &'static str,
+        amount_drops: &'static str,
+    }
+
+    impl Record for Sample {
+        const KIND: &'static str = "sample";
+
+        fn csv_columns(&self) -> Vec<(&'static str, String)> {
+            vec![("name", self.name.to_string()), ("amount_drops", self.amount_drops.to_string())]
+        }
+    }
+
+    const RECORDS: [Sample; 2] = [
+        Sample { name: "alice", amount_drops: "1000000" },
+        Sample { name: "bob", amount_drops: "25" },
+    ];
+
+    /// Вывод записей так же, как в `emit_list` (`list = true`) и `emit_one` (`list = false`)
+    fn render(format: OutputFormat, list: bool, records: &[Sample]) -> String {
+        let buffer = Buffer::default();
+        let mut writer = format.writer_to(list, Box::new(buffer.clone())).unwrap();
+        for record in records {
+            write_record(writer.as_mut(), record).unwrap();
+        }
+        writer.finish().unwrap();
+        let bytes = buffer.0.borrow().clone();
+        String::from_utf8(bytes).unwrap()
+    }
+
+    #[test]
+    fn envelope_comes_first_in_json() {
+        let value: Value = serde_json::from_str(&render(OutputFormat::Json, false, &RECORDS[..1])).unwrap();
+        let keys: Vec<_> = value.as_object().unwrap().keys().map(String::as_str).collect();
+        assert_eq!(keys, ["schema_version", "kind", "name", "amount_drops"]);
+        assert_eq!(value["schema_version"], SCHEMA_VERSION);
+        assert_eq!(value["kind"], "sample");
+    }
+
+    #[test]
+    fn envelope_comes_first_in_csv_header() {
+        let csv = render(OutputFormat::Csv, true, &RECORDS);
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines, ["schema_version,kind,name,amount_drops", "1,sample,alice,1000000", "1,sample,bob,25"]);
+    }
+
+    #[test]
+    fn emit_one_writes_single_object() {
+        let value: Value = serde_json::from_str(&render(OutputFormat::Json, false, &RECORDS[..1])).unwrap();
+        assert_eq!(value["name"], "alice");
+
+        let yaml: Value = serde_yaml::from_str(&render(OutputFormat::Yaml, false, &RECORDS[..1])).unwrap();
+        assert!(yaml.is_object());
+        assert_eq!(yaml["kind"], "sample");
+    }
+
+    #[test]
+    fn emit_list_writes_array() {
+        let value: Value = serde_json::from_str(&render(OutputFormat::Json, true, &RECORDS)).unwrap();
+        let items = value.as_array().unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1]["name"], "bob");
+
+        // Пустой список - пустой массив, а не null
+        assert_eq!(render(OutputFormat::Json, true, &[]).trim(), "[]");
+
+        let yaml: Value = serde_yaml::from_str(&render(OutputFormat::Yaml, true, &RECORDS)).unwrap();
+        assert_eq!(yaml.as_array().unwrap().len(), 2);
+
+        let ndjson = render(OutputFormat::Ndjson, true, &RECORDS);
+        let lines: Vec<Value> = ndjson.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["kind"], "sample");
+    }
+
+    #[test]
+    fn text_format_has_no_writer() {
+        assert!(OutputFormat::Text.writer_to(true, Box::new(Buffer::default())).is_none());
+    }
+}
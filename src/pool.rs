@@ -0,0 +1,176 @@
+// pool.rs - набор серверов rippled с отслеживанием их состояния
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Как долго результаты проверки состояния считаются актуальными
+const HEALTH_TTL: Duration = Duration::from_secs(60);
+
+/// На какое время сервер исключается из выбора после сбоя
+const DOWN_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Состояние одного сервера
+#[derive(Debug, Clone, Default)]
+struct EndpointHealth {
+    /// Последний валидированный леджер по данным server_info
+    validated_ledger: Option<u32>,
+    /// До этого момента сервер считается недоступным
+    down_until: Option<Instant>,
+    /// Время последней проверки server_info
+    checked_at: Option<Instant>,
+}
+
+impl EndpointHealth {
+    fn is_down(&self, now: Instant) -> bool {
+        self.down_until.is_some_and(|until| until > now)
+    }
+}
+
+/// Серверы rippled в порядке предпочтения с данными о доступности и синхронизации
+pub struct EndpointPool {
+    urls: Vec<String>,
+    health: Mutex<Vec<EndpointHealth>>,
+}
+
+impl EndpointPool {
+    pub fn new(urls: Vec<String>) -> Self {
+        assert!(!urls.is_empty(), "пул серверов не может быть пустым");
+        let health = vec![EndpointHealth::default(); urls.len()];
+        EndpointPool {
+            urls,
+            health: Mutex::new(health),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.urls.len()
+    }
+
+    pub fn url(&self, index: usize) -> &str {
+        &self.urls[index]
+    }
+
+    /// Нужно ли (пере)проверить состояние серверов перед выбором.
+    /// Для единственного сервера выбирать не из чего, проверка не выполняется.
+    pub fn needs_health_check(&self) -> bool {
+        if self.urls.len() < 2 {
+            return false;
+        }
+        let health = self.health.lock().expect("мьютекс пула не отравлен");
+        health
+            .iter()
+            .any(|h| h.checked_at.is_none_or(|checked| checked.elapsed() > HEALTH_TTL))
+    }
+
+    /// Записывает успешную проверку server_info
+    pub fn record_healthy(&self, index: usize, validated_ledger: Option<u32>) {
+        let mut health = self.health.lock().expect("мьютекс пула не отравлен");
+        let endpoint = &mut health[index];
+        endpoint.checked_at = Some(Instant::now());
+        endpoint.validated_ledger = validated_ledger;
+        endpoint.down_until = None;
+    }
+
+    /// Записывает неудачную проверку server_info
+    pub fn record_unhealthy(&self, index: usize) {
+        let now = Instant::now();
+        let mut health = self.health.lock().expect("мьютекс пула не отравлен");
+        let endpoint = &mut health[index];
+        endpoint.checked_at = Some(now);
+        endpoint.down_until = Some(now + DOWN_COOLDOWN);
+    }
+
+    /// Исключает сервер из выбора на время `DOWN_COOLDOWN`
+    pub fn mark_down(&self, index: usize) {
+        let mut health = self.health.lock().expect("мьютекс пула не отравлен");
+        health[index].down_until = Some(Instant::now() + DOWN_COOLDOWN);
+        log::warn!("Сервер {} помечен как недоступный", self.urls[index]);
+    }
+
+    /// Выбирает сервер для запроса: доступный с наибольшим валидированным леджером,
+    /// при равенстве - первый по порядку. Если недоступны все, берется тот,
+    /// который раньше других выйдет из исключения.
+    pub fn select(&self) -> usize {
+        let now = Instant::now();
+        let health = self.health.lock().expect("мьютекс пула не отравлен");
+
+        let available = health
+            .iter()
+            .enumerate()
+            .filter(|(_, h)| !h.is_down(now))
+            // max_by_key возвращает последний из равных, поэтому индекс сравнивается в обратном порядке
+            .max_by_key(|(index, h)| (h.validated_ledger, std::cmp::Reverse(*index)))
+            .map(|(index, _)| index);
+
+        available.unwrap_or_else(|| {
+            health
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, h)| h.down_until)
+                .map(|(index, _)| index)
+                .unwrap_or(0)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool_of(size: usize) -> EndpointPool {
+        EndpointPool::new((0..size).map(|i| format!("http://node{}:5005", i)).collect())
+    }
+
+    #[test]
+    fn prefers_first_server_without_health_data() {
+        assert_eq!(pool_of(3).select(), 0);
+    }
+
+    #[test]
+    fn prefers_most_recent_validated_ledger() {
+        let pool = pool_of(3);
+        pool.record_healthy(0, Some(100));
+        pool.record_healthy(1, Some(105));
+        pool.record_healthy(2, Some(103));
+        assert_eq!(pool.select(), 1);
+    }
+
+    #[test]
+    fn breaks_ties_by_order() {
+        let pool = pool_of(3);
+        pool.record_healthy(0, Some(100));
+        pool.record_healthy(1, Some(105));
+        pool.record_healthy(2, Some(105));
+        assert_eq!(pool.select(), 1);
+    }
+
+    #[test]
+    fn skips_servers_marked_down() {
+        let pool = pool_of(3);
+        pool.record_healthy(0, Some(105));
+        pool.record_healthy(1, Some(100));
+        pool.record_unhealthy(2);
+        pool.mark_down(0);
+        assert_eq!(pool.select(), 1);
+    }
+
+    #[test]
+    fn falls_back_to_server_recovering_first() {
+        let pool = pool_of(3);
+        pool.mark_down(1);
+        pool.mark_down(2);
+        pool.mark_down(0);
+        assert_eq!(pool.select(), 1);
+    }
+
+    #[test]
+    fn health_check_needed_until_all_checked() {
+        let pool = pool_of(2);
+        assert!(pool.needs_health_check());
+        pool.record_healthy(0, Some(100));
+        assert!(pool.needs_health_check());
+        pool.record_unhealthy(1);
+        assert!(!pool.needs_health_check());
+        // Для одного сервера выбирать не из чего
+        assert!(!pool_of(1).needs_health_check());
+    }
+}
@@ -0,0 +1,128 @@
+// retry.rs - политика повторных запросов к rippled
+use std::time::Duration;
+
+use clap::ValueEnum;
+
+/// Класс временного сбоя, после которого запрос имеет смысл повторить
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum RetryClass {
+    /// Ошибка соединения, таймаут, HTTP 5xx
+    Network,
+    /// Превышен лимит запросов: slowDown, HTTP 429
+    RateLimited,
+    /// Сервер перегружен или не синхронизирован: tooBusy, noNetwork, notSynced, HTTP 503
+    ServerBusy,
+}
+
+/// Политика повторов: экспоненциальная задержка со случайной составляющей
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Максимальное число попыток, включая первую
+    pub max_attempts: u32,
+    /// Задержка перед первым повтором
+    pub base_delay: Duration,
+    /// Верхняя граница задержки
+    pub max_delay: Duration,
+    /// Классы сбоев, после которых запрос повторяется
+    pub retry_on: Vec<RetryClass>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 4,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            retry_on: vec![RetryClass::Network, RetryClass::RateLimited, RetryClass::ServerBusy],
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Нужно ли повторять запрос после сбоя класса `class` на попытке `attempt` (с 1)
+    pub fn should_retry(&self, class: Option<RetryClass>, attempt: u32) -> bool {
+        attempt < self.max_attempts && class.is_some_and(|class| self.retry_on.contains(&class))
+    }
+
+    /// Задержка перед повтором после попытки `attempt` (с 1).
+    /// Половина задержки фиксирована, половина случайна, чтобы клиенты не повторяли запросы синхронно.
+    /// При превышении лимита задержка удваивается; `retry_after` от сервера (Retry-After) не сокращается.
+    pub fn delay(&self, attempt: u32, class: RetryClass, retry_after: Option<Duration>) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(16);
+        let mut backoff = self.base_delay.saturating_mul(1 << exponent);
+        if class == RetryClass::RateLimited {
+            backoff = backoff.saturating_mul(2);
+        }
+        let backoff = backoff.min(self.max_delay);
+        
+        let half = backoff / 2;
+        let jittered = half + half.mul_f64(rand::random::<f64>());
+        
+        match retry_after {
+            Some(retry_after) => jittered.max(retry_after.min(self.max_delay)),
+            None => jittered,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 4,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+            retry_on: vec![RetryClass::Network, RetryClass::ServerBusy],
+        }
+    }
+
+    fn assert_within(delay: Duration, backoff_ms: u64) {
+        let backoff = Duration::from_millis(backoff_ms);
+        assert!(delay >= backoff / 2 && delay <= backoff, "{:?} вне [{:?}, {:?}]", delay, backoff / 2, backoff);
+    }
+
+    #[test]
+    fn retries_only_listed_classes_within_attempt_limit() {
+        let policy = policy();
+        assert!(policy.should_retry(Some(RetryClass::Network), 1));
+        assert!(policy.should_retry(Some(RetryClass::ServerBusy), 3));
+        assert!(!policy.should_retry(Some(RetryClass::Network), 4));
+        assert!(!policy.should_retry(Some(RetryClass::RateLimited), 1));
+        assert!(!policy.should_retry(None, 1));
+    }
+
+    #[test]
+    fn backoff_doubles_with_jitter() {
+        let policy = policy();
+        for _ in 0..100 {
+            assert_within(policy.delay(1, RetryClass::Network, None), 100);
+            assert_within(policy.delay(2, RetryClass::Network, None), 200);
+            assert_within(policy.delay(3, RetryClass::Network, None), 400);
+        }
+    }
+
+    #[test]
+    fn rate_limit_doubles_backoff() {
+        for _ in 0..100 {
+            assert_within(policy().delay(2, RetryClass::RateLimited, None), 400);
+        }
+    }
+
+    #[test]
+    fn backoff_is_capped() {
+        for _ in 0..100 {
+            assert_within(policy().delay(30, RetryClass::Network, None), 1_000);
+        }
+    }
+
+    #[test]
+    fn honours_retry_after_up_to_max_delay() {
+        let policy = policy();
+        let delay = policy.delay(1, RetryClass::RateLimited, Some(Duration::from_millis(700)));
+        assert_eq!(delay, Duration::from_millis(700));
+        let delay = policy.delay(1, RetryClass::RateLimited, Some(Duration::from_secs(60)));
+        assert_eq!(delay, Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,108 @@
+// rippled_error.rs - коды ошибок, возвращаемые rippled в ответах JSON-RPC
+use std::fmt;
+
+use serde::Deserialize;
+
+/// Код ошибки из поля `error` ответа rippled.
+/// Неизвестные коды сохраняются в `Other`, чтобы не терять их при разборе.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(from = "String")]
+pub enum RippledError {
+    /// Аккаунт не существует в леджере
+    ActNotFound,
+    /// Некорректный адрес аккаунта
+    ActMalformed,
+    /// Леджер не найден
+    LgrNotFound,
+    /// Некорректный индекс леджера
+    LgrIdxMalformed,
+    /// Некорректный диапазон леджеров
+    LgrIdxsInvalid,
+    /// Диапазон леджеров слишком велик
+    ExcessiveLgrRange,
+    /// Транзакция не найдена
+    TxnNotFound,
+    /// Некорректные параметры запроса
+    InvalidParams,
+    /// Неизвестный метод
+    UnknownCmd,
+    /// Сервер перегружен
+    TooBusy,
+    /// Сервер не подключен к сети
+    NoNetwork,
+    /// Сервер не знает текущего леджера
+    NoCurrent,
+    /// Сервер не знает закрытого леджера
+    NoClosed,
+    /// Сервер не синхронизирован с сетью
+    NotSynced,
+    /// Превышен лимит запросов
+    SlowDown,
+    /// Сервер заблокирован поправкой (amendment blocked)
+    AmendmentBlocked,
+    /// Внутренняя ошибка сервера
+    Internal,
+    /// Доступ запрещен
+    Forbidden,
+    /// Прочие коды
+    Other(String),
+}
+
+impl RippledError {
+    /// Код ошибки в том виде, в каком его возвращает rippled
+    pub fn code(&self) -> &str {
+        match self {
+            RippledError::ActNotFound => "actNotFound",
+            RippledError::ActMalformed => "actMalformed",
+            RippledError::LgrNotFound => "lgrNotFound",
+            RippledError::LgrIdxMalformed => "lgrIdxMalformed",
+            RippledError::LgrIdxsInvalid => "lgrIdxsInvalid",
+            RippledError::ExcessiveLgrRange => "excessiveLgrRange",
+            RippledError::TxnNotFound => "txnNotFound",
+            RippledError::InvalidParams => "invalidParams",
+            RippledError::UnknownCmd => "unknownCmd",
+            RippledError::TooBusy => "tooBusy",
+            RippledError::NoNetwork => "noNetwork",
+            RippledError::NoCurrent => "noCurrent",
+            RippledError::NoClosed => "noClosed",
+            RippledError::NotSynced => "notSynced",
+            RippledError::SlowDown => "slowDown",
+            RippledError::AmendmentBlocked => "amendmentBlocked",
+            RippledError::Internal => "internal",
+            RippledError::Forbidden => "forbidden",
+            RippledError::Other(code) => code,
+        }
+    }
+}
+
+impl From<String> for RippledError {
+    fn from(code: String) -> Self {
+        match code.as_str() {
+            "actNotFound" => RippledError::ActNotFound,
+            "actMalformed" => RippledError::ActMalformed,
+            "lgrNotFound" => RippledError::LgrNotFound,
+            "lgrIdxMalformed" => RippledError::LgrIdxMalformed,
+            "lgrIdxsInvalid" => RippledError::LgrIdxsInvalid,
+            "excessiveLgrRange" => RippledError::ExcessiveLgrRange,
+            "txnNotFound" => RippledError::TxnNotFound,
+            "invalidParams" => RippledError::InvalidParams,
+            "unknownCmd" => RippledError::UnknownCmd,
+            "tooBusy" => RippledError::TooBusy,
+            "noNetwork" => RippledError::NoNetwork,
+            "noCurrent" => RippledError::NoCurrent,
+            "noClosed" => RippledError::NoClosed,
+            "notSynced" => RippledError::NotSynced,
+            "slowDown" => RippledError::SlowDown,
+            "amendmentBlocked" => RippledError::AmendmentBlocked,
+            "internal" => RippledError::Internal,
+            "forbidden" => RippledError::Forbidden,
+            _ => RippledError::Other(code),
+        }
+    }
+}
+
+impl fmt::Display for RippledError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
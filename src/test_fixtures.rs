@@ -0,0 +1,39 @@
+// test_fixtures.rs - общие данные для тестов разбора транзакций и метаданных
+use serde_json::{json, Value};
+
+/// Аккаунт, от имени которого строятся тестовые транзакции
+pub const ACCOUNT: &str = "rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh";
+
+/// Второй участник: получатель платежей
+pub const OTHER: &str = "r9cZA1mLK5R5Am25ArfXFmqgNwjZgnfk59";
+
+/// Payment от `ACCOUNT` к `OTHER` на сумму `amount`
+pub fn payment(amount: Value, flags: u32) -> Value {
+    json!({
+        "hash": "A1B2",
+        "Account": ACCOUNT,
+        "TransactionType": "Payment",
+        "Fee": "12",
+        "Flags": flags,
+        "Destination": OTHER,
+        "Amount": amount,
+    })
+}
+
+/// Метаданные успешной транзакции с узлами `nodes`
+pub fn meta(nodes: Value) -> Value {
+    json!({ "TransactionResult": "tesSUCCESS", "AffectedNodes": nodes })
+}
+
+/// Измененный узел AccountRoot; `previous` - баланс до транзакции, если он изменился
+pub fn account_root(account: &str, previous: Option<&str>, balance: &str) -> Value {
+    let mut node = json!({
+        "LedgerEntryType": "AccountRoot",
+        "LedgerIndex": "13F1A95D7AAB7108D5CE7EEAF504B2894B8C674E6D68499076441C4837282BF8",
+        "FinalFields": { "Account": account, "Balance": balance },
+    });
+    if let Some(previous) = previous {
+        node["PreviousFields"] = json!({ "Balance": previous });
+    }
+    json!({ "ModifiedNode": node })
+}
@@ -0,0 +1,181 @@
+// time.rs - преобразование времени леджера (Ripple epoch) и его форматирование
+use chrono::{DateTime, Local, NaiveDate, SecondsFormat, TimeZone, Utc};
+use clap::ValueEnum;
+
+/// Смещение Ripple epoch (2000-01-01T00:00:00Z) относительно Unix epoch в секундах
+pub const RIPPLE_EPOCH_OFFSET: i64 = 946_684_800;
+
+/// Переводит время леджера (секунды от Ripple epoch) в Unix-время; None при переполнении
+pub fn ripple_to_unix(ripple_time: u64) -> Option<i64> {
+    i64::try_from(ripple_time).ok()?.checked_add(RIPPLE_EPOCH_OFFSET)
+}
+
+/// Переводит Unix-время во время леджера; None для моментов раньше 2000 года
+pub fn unix_to_ripple(unix_time: i64) -> Option<u64> {
+    u64::try_from(unix_time - RIPPLE_EPOCH_OFFSET).ok()
+}
+
+/// Время леджера как дата UTC; None, если значение от сервера вне диапазона дат
+pub fn ripple_to_datetime(ripple_time: u64) -> Option<DateTime<Utc>> {
+    Utc.timestamp_opt(ripple_to_unix(ripple_time)?, 0).single()
+}
+
+/// Дата как время леджера; None для моментов раньше 2000 года
+pub fn datetime_to_ripple(datetime: &DateTime<Utc>) -> Option<u64> {
+    unix_to_ripple(datetime.timestamp())
+}
+
+/// Разбирает момент времени из аргумента командной строки:
+/// RFC 3339 (`2024-05-01T12:00:00Z`) или дата (`2024-05-01`, начало дня UTC)
+pub fn parse_datetime(input: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(input) {
+        return Ok(datetime.with_timezone(&Utc));
+    }
+
+    NaiveDate::parse_from_str(input, "%Y-%m-%d")
+        .map(|date| date.and_hms_opt(0, 0, 0).expect("полночь всегда валидна").and_utc())
+        .map_err(|_| format!("ожидалась дата RFC 3339 или YYYY-MM-DD, получено '{}'", input))
+}
+
+/// Часовой пояс для вывода дат
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum TimeZoneMode {
+    #[default]
+    Utc,
+    /// Локальный часовой пояс системы
+    Local,
+}
+
+/// Стиль вывода дат
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum TimeStyle {
+    /// `2024-05-01 12:00:00 UTC`
+    #[default]
+    Default,
+    /// `2024-05-01T12:00:00Z`
+    Rfc3339,
+    /// `3 часа назад`
+    Relative,
+}
+
+/// Форматирование дат с учетом часового пояса и стиля
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TimeFormatter {
+    pub zone: TimeZoneMode,
+    pub style: TimeStyle,
+}
+
+impl TimeFormatter {
+    pub fn new(zone: TimeZoneMode, style: TimeStyle) -> Self {
+        TimeFormatter { zone, style }
+    }
+
+    pub fn format(&self, datetime: &DateTime<Utc>) -> String {
+        match (self.style, self.zone) {
+            (TimeStyle::Relative, _) => format_relative(datetime, &Utc::now()),
+            (TimeStyle::Rfc3339, TimeZoneMode::Utc) => datetime.to_rfc3339_opts(SecondsFormat::Secs, true),
+            (TimeStyle::Rfc3339, TimeZoneMode::Local) => {
+                datetime.with_timezone(&Local).to_rfc3339_opts(SecondsFormat::Secs, true)
+            }
+            (TimeStyle::Default, TimeZoneMode::Utc) => datetime.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+            (TimeStyle::Default, TimeZoneMode::Local) => {
+                datetime.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S %:z").to_string()
+            }
+        }
+    }
+}
+
+/// Относительное время: "3 часа назад", "через 2 дня", "только что"
+fn format_relative(datetime: &DateTime<Utc>, now: &DateTime<Utc>) -> String {
+    let seconds = (*now - *datetime).num_seconds();
+    let abs = seconds.unsigned_abs();
+
+    let (value, forms) = match abs {
+        0..=59 => return "только что".to_string(),
+        60..=3_599 => (abs / 60, ["минуту", "минуты", "минут"]),
+        3_600..=86_399 => (abs / 3_600, ["час", "часа", "часов"]),
+        86_400..=2_591_999 => (abs / 86_400, ["день", "дня", "дней"]),
+        2_592_000..=31_535_999 => (abs / 2_592_000, ["месяц", "месяца", "месяцев"]),
+        _ => (abs / 31_536_000, ["год", "года", "лет"]),
+    };
+    let unit = plural_form(value, forms);
+
+    if seconds >= 0 {
+        format!("{} {} назад", value, unit)
+    } else {
+        format!("через {} {}", value, unit)
+    }
+}
+
+/// Форма существительного для числа: 1 час, 2 часа, 5 часов (11-14 - всегда третья форма)
+fn plural_form(value: u64, [one, few, many]: [&'static str; 3]) -> &'static str {
+    match (value % 10, value % 100) {
+        (_, 11..=14) => many,
+        (1, _) => one,
+        (2..=4, _) => few,
+        _ => many,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn converts_ripple_epoch() {
+        assert_eq!(ripple_to_unix(0), Some(RIPPLE_EPOCH_OFFSET));
+        assert_eq!(ripple_to_datetime(0).unwrap().to_rfc3339(), "2000-01-01T00:00:00+00:00");
+        assert_eq!(ripple_to_unix(779_907_201), Some(1_726_592_001));
+        assert_eq!(ripple_to_datetime(779_907_201).unwrap().to_rfc3339(), "2024-09-17T16:53:21+00:00");
+    }
+
+    #[test]
+    fn rejects_out_of_range_ripple_time() {
+        assert_eq!(ripple_to_unix(u64::MAX), None);
+        assert_eq!(ripple_to_unix(i64::MAX as u64), None);
+        assert_eq!(ripple_to_datetime(u64::MAX), None);
+        assert_eq!(ripple_to_datetime(1 << 45), None);
+    }
+
+    #[test]
+    fn round_trips_ripple_time() {
+        let datetime = ripple_to_datetime(779_907_201).unwrap();
+        assert_eq!(datetime_to_ripple(&datetime), Some(779_907_201));
+        assert_eq!(unix_to_ripple(RIPPLE_EPOCH_OFFSET), Some(0));
+        assert_eq!(unix_to_ripple(RIPPLE_EPOCH_OFFSET - 1), None);
+    }
+
+    #[test]
+    fn parses_command_line_dates() {
+        assert_eq!(parse_datetime("2024-05-01").unwrap().to_rfc3339(), "2024-05-01T00:00:00+00:00");
+        assert_eq!(
+            parse_datetime("2024-05-01T15:00:00+03:00").unwrap().to_rfc3339(),
+            "2024-05-01T12:00:00+00:00"
+        );
+        assert!(parse_datetime("01.05.2024").is_err());
+    }
+
+    #[test]
+    fn formats_relative_time_in_russian() {
+        let now = ripple_to_datetime(779_907_201).unwrap();
+        let ago = |seconds| format_relative(&(now - Duration::seconds(seconds)), &now);
+        assert_eq!(ago(30), "только что");
+        assert_eq!(ago(60), "1 минуту назад");
+        assert_eq!(ago(3 * 3_600), "3 часа назад");
+        assert_eq!(ago(5 * 3_600), "5 часов назад");
+        assert_eq!(ago(21 * 86_400), "21 день назад");
+        assert_eq!(ago(11 * 86_400), "11 дней назад");
+        assert_eq!(ago(2 * 31_536_000), "2 года назад");
+        assert_eq!(ago(-2 * 86_400), "через 2 дня");
+    }
+
+    #[test]
+    fn chooses_plural_forms() {
+        let forms = ["год", "года", "лет"];
+        let cases = [(1, "год"), (2, "года"), (5, "лет"), (11, "лет"), (12, "лет"), (21, "год"), (104, "года"), (111, "лет")];
+        for (value, expected) in cases {
+            assert_eq!(plural_form(value, forms), expected, "{}", value);
+        }
+    }
+}
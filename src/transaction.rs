@@ -0,0 +1,392 @@
+// transaction.rs - типизированные транзакции XRPL
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::amount::{Amount, Asset, Drops};
+use crate::time;
+
+/// Флаг Payment: частичный платеж, получатель может получить меньше Amount
+pub const TF_PARTIAL_PAYMENT: u32 = 0x0002_0000;
+
+/// Транзакция XRPL: общие поля и данные конкретного типа
+#[derive(Deserialize, Debug, Clone)]
+#[serde(try_from = "Value")]
+pub struct Transaction {
+    pub hash: String,
+    pub account: String,
+    pub transaction_type: String,
+    pub fee: Option<Drops>,
+    pub sequence: Option<u32>,
+    pub flags: u32,
+    pub date: Option<u64>,
+    pub ledger_index: Option<u32>,
+    pub memos: Vec<Memo>,
+    pub kind: TransactionKind,
+}
+
+/// Общие поля, присутствующие во всех транзакциях
+#[derive(Deserialize, Debug)]
+struct TransactionCommon {
+    hash: String,
+    #[serde(rename = "Account")]
+    account: String,
+    #[serde(rename = "TransactionType")]
+    transaction_type: String,
+    #[serde(rename = "Fee")]
+    fee: Option<Drops>,
+    #[serde(rename = "Sequence")]
+    sequence: Option<u32>,
+    #[serde(rename = "Flags", default)]
+    flags: u32,
+    date: Option<u64>,
+    ledger_index: Option<u32>,
+    #[serde(rename = "Memos", default)]
+    memos: Vec<MemoWrapper>,
+}
+
+impl TryFrom<Value> for Transaction {
+    type Error = serde_json::Error;
+
+    fn try_from(mut value: Value) -> Result<Self, Self::Error> {
+        // API v2 передает Amount платежа только как DeliverMax; API v1 - оба поля с одним значением.
+        // `#[serde(alias)]` отверг бы ответ v1 как дублирующий поле, поэтому DeliverMax переносится вручную.
+        if let Some(fields) = value.as_object_mut() {
+            if !fields.contains_key("Amount") {
+                if let Some(deliver_max) = fields.remove("DeliverMax") {
+                    fields.insert("Amount".to_string(), deliver_max);
+                }
+            }
+        }
+
+        let common = TransactionCommon::deserialize(&value)?;
+        // Неразобранные поля конкретного типа не должны скрывать всю транзакцию:
+        // общие поля остаются, а тип показывается как неизвестный
+        let kind = TransactionKind::deserialize(&value).unwrap_or_else(|e| {
+            log::warn!("Не удалось разобрать поля {} {}: {}", common.transaction_type, common.hash, e);
+            TransactionKind::Unknown
+        });
+
+        Ok(Transaction {
+            hash: common.hash,
+            account: common.account,
+            transaction_type: common.transaction_type,
+            fee: common.fee,
+            sequence: common.sequence,
+            flags: common.flags,
+            date: common.date,
+            ledger_index: common.ledger_index,
+            memos: common.memos.into_iter().map(|wrapper| wrapper.memo).collect(),
+            kind,
+        })
+    }
+}
+
+/// Заметка к транзакции; все поля в hex
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Memo {
+    pub memo_type: Option<String>,
+    pub memo_data: Option<String>,
+    pub memo_format: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct MemoWrapper {
+    #[serde(rename = "Memo")]
+    memo: Memo,
+}
+
+/// Данные транзакции в зависимости от её типа (поле `TransactionType`)
+#[derive(Deserialize, Debug, Clone)]
+#[serde(tag = "TransactionType")]
+pub enum TransactionKind {
+    #[serde(rename_all = "PascalCase")]
+    Payment {
+        destination: String,
+        amount: Amount,
+        destination_tag: Option<u32>,
+    },
+
+    #[serde(rename_all = "PascalCase")]
+    OfferCreate {
+        taker_gets: Amount,
+        taker_pays: Amount,
+    },
+
+    #[serde(rename_all = "PascalCase")]
+    OfferCancel {
+        offer_sequence: u32,
+    },
+
+    #[serde(rename_all = "PascalCase")]
+    TrustSet {
+        limit_amount: Amount,
+    },
+
+    #[serde(rename_all = "PascalCase")]
+    EscrowCreate {
+        destination: String,
+        amount: Amount,
+    },
+
+    #[serde(rename_all = "PascalCase")]
+    EscrowFinish {
+        owner: String,
+        offer_sequence: u32,
+    },
+
+    #[serde(rename_all = "PascalCase")]
+    EscrowCancel {
+        owner: String,
+        offer_sequence: u32,
+    },
+
+    #[serde(rename_all = "PascalCase")]
+    PaymentChannelCreate {
+        destination: String,
+        amount: Amount,
+        settle_delay: u32,
+    },
+
+    #[serde(rename_all = "PascalCase")]
+    PaymentChannelFund {
+        channel: String,
+        amount: Amount,
+    },
+
+    #[serde(rename_all = "PascalCase")]
+    PaymentChannelClaim {
+        channel: String,
+        balance: Option<Amount>,
+    },
+
+    #[serde(rename_all = "PascalCase")]
+    CheckCreate {
+        destination: String,
+        send_max: Amount,
+    },
+
+    #[serde(rename_all = "PascalCase")]
+    CheckCash {
+        #[serde(rename = "CheckID")]
+        check_id: String,
+        amount: Option<Amount>,
+        deliver_min: Option<Amount>,
+    },
+
+    #[serde(rename_all = "PascalCase")]
+    CheckCancel {
+        #[serde(rename = "CheckID")]
+        check_id: String,
+    },
+
+    #[serde(rename_all = "PascalCase")]
+    NFTokenMint {
+        #[serde(rename = "NFTokenTaxon")]
+        nftoken_taxon: u32,
+        transfer_fee: Option<u16>,
+    },
+
+    #[serde(rename_all = "PascalCase")]
+    NFTokenBurn {
+        #[serde(rename = "NFTokenID")]
+        nftoken_id: String,
+    },
+
+    #[serde(rename_all = "PascalCase")]
+    NFTokenCreateOffer {
+        #[serde(rename = "NFTokenID")]
+        nftoken_id: String,
+        amount: Amount,
+        destination: Option<String>,
+    },
+
+    #[serde(rename_all = "PascalCase")]
+    NFTokenCancelOffer {
+        #[serde(rename = "NFTokenOffers")]
+        nftoken_offers: Vec<String>,
+    },
+
+    #[serde(rename_all = "PascalCase")]
+    NFTokenAcceptOffer {
+        #[serde(rename = "NFTokenSellOffer")]
+        nftoken_sell_offer: Option<String>,
+        #[serde(rename = "NFTokenBuyOffer")]
+        nftoken_buy_offer: Option<String>,
+    },
+
+    #[serde(rename_all = "PascalCase")]
+    AMMCreate {
+        amount: Amount,
+        amount2: Amount,
+        trading_fee: u16,
+    },
+
+    #[serde(rename_all = "PascalCase")]
+    AMMDeposit {
+        asset: Asset,
+        asset2: Asset,
+    },
+
+    #[serde(rename_all = "PascalCase")]
+    AMMWithdraw {
+        asset: Asset,
+        asset2: Asset,
+    },
+
+    #[serde(rename_all = "PascalCase")]
+    AMMVote {
+        asset: Asset,
+        asset2: Asset,
+        trading_fee: u16,
+    },
+
+    #[serde(rename_all = "PascalCase")]
+    AMMBid {
+        asset: Asset,
+        asset2: Asset,
+    },
+
+    #[serde(rename_all = "PascalCase")]
+    AMMDelete {
+        asset: Asset,
+        asset2: Asset,
+    },
+
+    #[serde(rename_all = "PascalCase")]
+    AccountSet {
+        set_flag: Option<u32>,
+        clear_flag: Option<u32>,
+        domain: Option<String>,
+    },
+
+    #[serde(rename_all = "PascalCase")]
+    AccountDelete {
+        destination: String,
+    },
+
+    #[serde(rename_all = "PascalCase")]
+    SetRegularKey {
+        regular_key: Option<String>,
+    },
+
+    #[serde(rename_all = "PascalCase")]
+    SignerListSet {
+        signer_quorum: u32,
+        /// Для сводки нужно только число подписантов
+        signer_entries: Option<Vec<Value>>,
+    },
+
+    #[serde(rename_all = "PascalCase")]
+    TicketCreate {
+        ticket_count: u32,
+    },
+
+    #[serde(rename_all = "PascalCase")]
+    DepositPreauth {
+        authorize: Option<String>,
+        unauthorize: Option<String>,
+    },
+
+    #[serde(rename_all = "PascalCase")]
+    Clawback {
+        amount: Amount,
+    },
+
+    /// Тип, не поддерживаемый программой (псевдотранзакции, новые поправки)
+    #[serde(other)]
+    Unknown,
+}
+
+impl Transaction {
+    /// Получатель транзакции, если он есть у данного типа
+    pub fn destination(&self) -> Option<&str> {
+        match &self.kind {
+            TransactionKind::Payment { destination, .. }
+            | TransactionKind::EscrowCreate { destination, .. }
+            | TransactionKind::PaymentChannelCreate { destination, .. }
+            | TransactionKind::CheckCreate { destination, .. }
+            | TransactionKind::AccountDelete { destination, .. } => Some(destination),
+            TransactionKind::NFTokenCreateOffer { destination, .. } => destination.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Сумма платежа (XRP, токен или MPT); для прочих типов - None
+    pub fn amount(&self) -> Option<&Amount> {
+        match &self.kind {
+            TransactionKind::Payment { amount, .. } => Some(amount),
+            _ => None,
+        }
+    }
+
+    /// Частичный платеж (флаг tfPartialPayment): поле Amount - лишь верхняя граница,
+    /// фактическую сумму показывает delivered_amount в метаданных
+    pub fn is_partial_payment(&self) -> bool {
+        matches!(self.kind, TransactionKind::Payment { .. }) && self.flags & TF_PARTIAL_PAYMENT != 0
+    }
+
+    /// Время закрытия леджера с транзакцией
+    pub fn datetime(&self) -> Option<DateTime<Utc>> {
+        self.date.and_then(time::ripple_to_datetime)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{self, OTHER};
+    use serde_json::json;
+
+    fn payment(amount: Value) -> Value {
+        test_fixtures::payment(amount, TF_PARTIAL_PAYMENT)
+    }
+
+    #[test]
+    fn parses_payment() {
+        let tx: Transaction = serde_json::from_value(payment(json!("1000000"))).unwrap();
+        assert_eq!(tx.destination(), Some(OTHER));
+        assert!(tx.amount().is_some());
+        assert!(tx.is_partial_payment());
+    }
+
+    #[test]
+    fn reads_amount_from_deliver_max() {
+        // API v2: только DeliverMax
+        let mut value = payment(json!("1000000"));
+        let amount = value.as_object_mut().unwrap().remove("Amount").unwrap();
+        value["DeliverMax"] = amount;
+        let tx: Transaction = serde_json::from_value(value.clone()).unwrap();
+        assert!(matches!(tx.amount(), Some(Amount::Xrp(Drops(1_000_000)))));
+
+        // API v1: Amount и DeliverMax вместе
+        value["Amount"] = json!("1000000");
+        let tx: Transaction = serde_json::from_value(value).unwrap();
+        assert!(matches!(tx.amount(), Some(Amount::Xrp(Drops(1_000_000)))));
+    }
+
+    #[test]
+    fn falls_back_to_unknown_kind() {
+        let tx: Transaction = serde_json::from_value(payment(json!({ "unexpected": true }))).unwrap();
+        assert!(matches!(tx.kind, TransactionKind::Unknown));
+        assert_eq!(tx.transaction_type, "Payment");
+        assert_eq!(tx.hash, "A1B2");
+        assert!(tx.amount().is_none());
+    }
+
+    #[test]
+    fn unsupported_type_is_unknown() {
+        let mut value = payment(json!("1"));
+        value["TransactionType"] = json!("EnableAmendment");
+        let tx: Transaction = serde_json::from_value(value).unwrap();
+        assert!(matches!(tx.kind, TransactionKind::Unknown));
+    }
+
+    #[test]
+    fn missing_common_fields_are_an_error() {
+        let mut value = payment(json!("1"));
+        value.as_object_mut().unwrap().remove("Account");
+        assert!(serde_json::from_value::<Transaction>(value).is_err());
+    }
+}
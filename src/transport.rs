@@ -0,0 +1,393 @@
+// transport.rs - доставка запросов к rippled: JSON-RPC по HTTP или WebSocket
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use futures::{SinkExt, StreamExt};
+use reqwest::Client;
+use serde_json::{json, Map, Value};
+use tokio::net::TcpStream;
+use tokio::sync::{mpsc, oneshot};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+use crate::error::ViewerError;
+
+type Result<T> = std::result::Result<T, ViewerError>;
+
+/// Описание ошибки для запросов к закрытому соединению
+const CONNECTION_CLOSED: &str = "соединение WebSocket закрыто";
+
+/// Способ доставки запроса серверу rippled.
+/// Запрос и ответ передаются в форме JSON-RPC (`{"method", "params": [...]}` и `{"result": {...}}`),
+/// транспорт сам переводит их в свой протокол.
+pub trait Transport: Send + Sync {
+    fn request(&self, body: Value, timeout: Duration) -> BoxFuture<'_, Result<Value>>;
+
+    /// Канал сообщений потоков подписки (без `id`), закрывается при разрыве соединения.
+    /// Запрос `subscribe` после этого нужно отправить через этот же транспорт.
+    fn events(&self) -> BoxFuture<'_, Result<mpsc::UnboundedReceiver<Value>>> {
+        Box::pin(async {
+            Err(ViewerError::Validation(
+                "подписка на потоки доступна только через WebSocket (ws:// или wss://)".to_string(),
+            ))
+        })
+    }
+}
+
+/// Транспорт по схеме URL: ws:// и wss:// - WebSocket, остальные - HTTP
+pub fn for_url(client: &Client, url: &str) -> Box<dyn Transport> {
+    if url.starts_with("ws://") || url.starts_with("wss://") {
+        Box::new(WsTransport::new(url))
+    } else {
+        Box::new(HttpTransport::new(client.clone(), url))
+    }
+}
+
+/// JSON-RPC: каждый запрос - отдельный POST
+pub struct HttpTransport {
+    client: Client,
+    url: String,
+}
+
+impl HttpTransport {
+    pub fn new(client: Client, url: &str) -> Self {
+        HttpTransport {
+            client,
+            url: url.to_string(),
+        }
+    }
+}
+
+impl Transport for HttpTransport {
+    fn request(&self, body: Value, timeout: Duration) -> BoxFuture<'_, Result<Value>> {
+        Box::pin(async move {
+            let response = self.client.post(&self.url).json(&body).timeout(timeout).send().await?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let retry_after = response
+                    .headers()
+                    .get(reqwest::header::RETRY_AFTER)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| value.parse::<u64>().ok())
+                    .map(Duration::from_secs);
+                let error_text = response.text().await.unwrap_or_else(|_| "Неизвестная ошибка".to_string());
+                log::debug!("API вернул ошибку {}: {}", status, error_text);
+                return Err(ViewerError::Http { status: status.as_u16(), retry_after });
+            }
+
+            let body = response.text().await?;
+            Ok(serde_json::from_str(&body)?)
+        })
+    }
+}
+
+/// WebSocket: одно долгоживущее соединение, ответы сопоставляются с запросами по `id`.
+/// Соединение открывается при первом запросе и переоткрывается после разрыва.
+pub struct WsTransport {
+    url: String,
+    next_id: AtomicU64,
+    connection: tokio::sync::Mutex<Option<Arc<WsConnection>>>,
+}
+
+impl WsTransport {
+    pub fn new(url: &str) -> Self {
+        WsTransport {
+            url: url.to_string(),
+            next_id: AtomicU64::new(1),
+            connection: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    /// Текущее соединение или новое, если прежнее закрыто
+    async fn connection(&self) -> Result<Arc<WsConnection>> {
+        let mut current = self.connection.lock().await;
+        if let Some(connection) = current.as_ref() {
+            if !connection.is_closed() {
+                return Ok(Arc::clone(connection));
+            }
+        }
+
+        log::debug!("Подключение WebSocket к {}", self.url);
+        let (stream, _) = tokio_tungstenite::connect_async(self.url.as_str())
+            .await
+            .map_err(|e| self.network_error(e))?;
+        let connection = Arc::new(WsConnection::spawn(stream));
+        *current = Some(Arc::clone(&connection));
+        Ok(connection)
+    }
+
+    async fn exchange(&self, body: Value) -> Result<Value> {
+        let connection = self.connection().await?;
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let reply = connection
+            .send(id, to_ws_command(&body, id))
+            .ok_or_else(|| self.network_error(CONNECTION_CLOSED))?;
+        // При таймауте future отменяется, и запрос снимается с ожидания при сбросе `_waiting`
+        let _waiting = Waiting { pending: &connection.pending, id };
+        let response = reply.await.map_err(|_| self.network_error(CONNECTION_CLOSED))?;
+        Ok(from_ws_response(response))
+    }
+
+    fn network_error(&self, error: impl fmt::Display) -> ViewerError {
+        ViewerError::Network(format!("{}: {}", self.url, error))
+    }
+}
+
+impl Transport for WsTransport {
+    fn request(&self, body: Value, timeout: Duration) -> BoxFuture<'_, Result<Value>> {
+        Box::pin(async move {
+            tokio::time::timeout(timeout, self.exchange(body))
+                .await
+                .map_err(|_| self.network_error(format!("нет ответа за {} с", timeout.as_secs())))?
+        })
+    }
+
+    fn events(&self) -> BoxFuture<'_, Result<mpsc::UnboundedReceiver<Value>>> {
+        Box::pin(async move { Ok(self.connection().await?.listen()) })
+    }
+}
+
+/// Запросы, ожидающие ответа, по `id`, и получатель сообщений потоков
+#[derive(Default)]
+struct Pending {
+    waiters: HashMap<u64, oneshot::Sender<Value>>,
+    events: Option<mpsc::UnboundedSender<Value>>,
+    closed: bool,
+}
+
+/// Снимает запрос с ожидания, если ответ так и не пришел (таймаут, отмена)
+struct Waiting<'a> {
+    pending: &'a Mutex<Pending>,
+    id: u64,
+}
+
+impl Drop for Waiting<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.waiters.remove(&self.id);
+        }
+    }
+}
+
+/// Открытое соединение: очередь сообщений на отправку и ожидающие запросы.
+/// Чтение и запись идут в отдельных задачах; на ping отвечает tungstenite.
+struct WsConnection {
+    outgoing: mpsc::UnboundedSender<Message>,
+    pending: Arc<Mutex<Pending>>,
+}
+
+impl WsConnection {
+    fn spawn(stream: WebSocketStream<MaybeTlsStream<TcpStream>>) -> Self {
+        let (mut writer, mut reader) = stream.split();
+        let (outgoing, mut queue) = mpsc::unbounded_channel();
+        let pending = Arc::new(Mutex::new(Pending::default()));
+
+        tokio::spawn(async move {
+            while let Some(message) = queue.recv().await {
+                let close = matches!(message, Message::Close(_));
+                if let Err(e) = writer.send(message).await {
+                    log::debug!("Ошибка отправки WebSocket: {}", e);
+                    break;
+                }
+                if close {
+                    break;
+                }
+            }
+        });
+
+        let replies = Arc::clone(&pending);
+        tokio::spawn(async move {
+            while let Some(message) = reader.next().await {
+                match message {
+                    Ok(Message::Text(text)) => dispatch(&replies, &text),
+                    Ok(Message::Close(_)) => {
+                        log::debug!("Сервер закрыл соединение WebSocket");
+                        break;
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        log::debug!("Соединение WebSocket прервано: {}", e);
+                        break;
+                    }
+                }
+            }
+
+            // Сброс отправителей завершает ожидающие запросы ошибкой, а поток сообщений - концом
+            let mut pending = replies.lock().expect("мьютекс ожидающих запросов отравлен");
+            pending.closed = true;
+            pending.waiters.clear();
+            pending.events = None;
+        });
+
+        WsConnection { outgoing, pending }
+    }
+
+    fn is_closed(&self) -> bool {
+        self.pending.lock().expect("мьютекс ожидающих запросов отравлен").closed
+    }
+
+    /// Направляет сообщения потоков в новый канал (прежний закрывается)
+    fn listen(&self) -> mpsc::UnboundedReceiver<Value> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let mut pending = self.pending.lock().expect("мьютекс ожидающих запросов отравлен");
+        if !pending.closed {
+            pending.events = Some(sender);
+        }
+        receiver
+    }
+
+    /// Регистрирует запрос и ставит его в очередь; ответ придет в возвращаемый канал.
+    /// None - соединение уже закрыто.
+    fn send(&self, id: u64, command: Value) -> Option<oneshot::Receiver<Value>> {
+        let (waiter, reply) = oneshot::channel();
+        {
+            let mut pending = self.pending.lock().expect("мьютекс ожидающих запросов отравлен");
+            if pending.closed {
+                return None;
+            }
+            pending.waiters.insert(id, waiter);
+        }
+        if self.outgoing.send(Message::text(command.to_string())).is_err() {
+            self.pending.lock().expect("мьютекс ожидающих запросов отравлен").waiters.remove(&id);
+            return None;
+        }
+        Some(reply)
+    }
+}
+
+impl Drop for WsConnection {
+    fn drop(&mut self) {
+        let _ = self.outgoing.send(Message::Close(None));
+    }
+}
+
+/// Передает ответ запросу с тем же `id`, а сообщения потоков - подписчику
+fn dispatch(pending: &Mutex<Pending>, text: &str) {
+    let message: Value = match serde_json::from_str(text) {
+        Ok(message) => message,
+        Err(e) => {
+            log::warn!("Некорректное сообщение WebSocket: {}", e);
+            return;
+        }
+    };
+
+    let mut pending = pending.lock().expect("мьютекс ожидающих запросов отравлен");
+    let Some(id) = message["id"].as_u64() else {
+        // Сообщение потока подписки
+        match &pending.events {
+            Some(events) => {
+                let _ = events.send(message);
+            }
+            None => log::debug!("Сообщение потока {} без подписчика", message["type"]),
+        }
+        return;
+    };
+
+    match pending.waiters.remove(&id) {
+        Some(waiter) => {
+            let _ = waiter.send(message);
+        }
+        None => log::debug!("Ответ WebSocket на неизвестный запрос {}", id),
+    }
+}
+
+/// `{"method": m, "params": [p]}` -> `{"id": id, "command": m, ...p}`
+fn to_ws_command(body: &Value, id: u64) -> Value {
+    let mut command = match body["params"].get(0) {
+        Some(Value::Object(params)) => params.clone(),
+        _ => Map::new(),
+    };
+    command.insert("id".to_string(), id.into());
+    command.insert("command".to_string(), body["method"].clone());
+    Value::Object(command)
+}
+
+/// Ответ WebSocket -> форма JSON-RPC: у ошибок поля `error*` лежат
+/// на верхнем уровне, а не в `result`
+fn from_ws_response(mut response: Value) -> Value {
+    if response["status"] == "error" {
+        let mut result = Map::new();
+        for field in ["status", "error", "error_code", "error_message"] {
+            if let Some(value) = response.get(field) {
+                result.insert(field.to_string(), value.clone());
+            }
+        }
+        return json!({ "result": result });
+    }
+
+    let status = response["status"].take();
+    let mut result = response["result"].take();
+    if let Value::Object(fields) = &mut result {
+        fields.entry("status").or_insert(status);
+    }
+    json!({ "result": result })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn converts_json_rpc_to_ws_command() {
+        let body = json!({ "method": "account_info", "params": [{ "account": "rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh" }] });
+        assert_eq!(
+            to_ws_command(&body, 7),
+            json!({ "account": "rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh", "id": 7, "command": "account_info" })
+        );
+        assert_eq!(to_ws_command(&json!({ "method": "server_info" }), 1), json!({ "id": 1, "command": "server_info" }));
+    }
+
+    #[test]
+    fn converts_ws_result_to_json_rpc() {
+        let response = json!({ "id": 7, "status": "success", "type": "response", "result": { "ledger_index": 5 } });
+        assert_eq!(from_ws_response(response), json!({ "result": { "ledger_index": 5, "status": "success" } }));
+    }
+
+    #[test]
+    fn converts_ws_error_to_json_rpc() {
+        let response = json!({
+            "id": 7,
+            "status": "error",
+            "type": "response",
+            "error": "actNotFound",
+            "error_code": 19,
+            "error_message": "Account not found.",
+            "request": { "command": "account_info" },
+        });
+        assert_eq!(
+            from_ws_response(response),
+            json!({ "result": {
+                "status": "error",
+                "error": "actNotFound",
+                "error_code": 19,
+                "error_message": "Account not found.",
+            } })
+        );
+    }
+
+    #[tokio::test]
+    async fn timed_out_request_stops_waiting() {
+        // Сервер принимает соединение, но не отвечает на запросы
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+            while socket.next().await.is_some() {}
+        });
+
+        let transport = WsTransport::new(&url);
+        let body = json!({ "method": "server_info", "params": [{}] });
+        let result = transport.request(body, Duration::from_millis(100)).await;
+        assert!(matches!(result, Err(ViewerError::Network(_))));
+
+        let connection = transport.connection().await.unwrap();
+        assert!(connection.pending.lock().unwrap().waiters.is_empty());
+    }
+}
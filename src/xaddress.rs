@@ -0,0 +1,182 @@
+// xaddress.rs - кодирование и декодирование X-адресов (XLS-5d)
+use thiserror::Error;
+
+use crate::codec::{self, CodecError};
+
+/// Префикс X-адреса основной сети (адрес начинается с 'X')
+const MAINNET_PREFIX: [u8; 2] = [0x05, 0x44];
+
+/// Префикс X-адреса тестовой сети (адрес начинается с 'T')
+const TESTNET_PREFIX: [u8; 2] = [0x04, 0x93];
+
+/// Длина данных: префикс (2) + AccountID (20) + флаг (1) + тег (8)
+const X_ADDRESS_DATA_LEN: usize = 31;
+
+/// Ошибки разбора X-адреса
+#[derive(Error, Debug)]
+pub enum XAddressError {
+    #[error("{0}")]
+    Codec(#[from] CodecError),
+
+    #[error("Неизвестный префикс X-адреса")]
+    InvalidPrefix,
+
+    #[error("Неверная длина X-адреса: {0} байт")]
+    InvalidLength(usize),
+
+    #[error("Неверный флаг тега: {0}")]
+    InvalidTagFlag(u8),
+
+    #[error("64-битные теги назначения не поддерживаются")]
+    UnsupportedTag,
+}
+
+/// Разобранный X-адрес
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XAddress {
+    /// Классический адрес `r...`
+    pub classic_address: String,
+    /// Тег назначения, если указан
+    pub tag: Option<u32>,
+    /// Адрес предназначен для тестовой сети
+    pub is_test: bool,
+}
+
+impl XAddress {
+    /// Кодирует X-адрес в строку
+    pub fn encode(&self) -> String {
+        let account_id = codec::decode_account_id(&self.classic_address)
+            .expect("XAddress всегда содержит валидный классический адрес");
+
+        let mut data = Vec::with_capacity(X_ADDRESS_DATA_LEN);
+        data.extend_from_slice(if self.is_test { &TESTNET_PREFIX } else { &MAINNET_PREFIX });
+        data.extend_from_slice(&account_id);
+        data.push(self.tag.is_some() as u8);
+        data.extend_from_slice(&self.tag.unwrap_or(0).to_le_bytes());
+        // Старшие 32 бита тега зарезервированы и всегда равны нулю
+        data.extend_from_slice(&[0u8; 4]);
+
+        codec::encode_check(&data)
+    }
+
+    /// Декодирует строку X-адреса
+    pub fn decode(x_address: &str) -> Result<Self, XAddressError> {
+        let data = codec::decode_check(x_address)?;
+        if data.len() != X_ADDRESS_DATA_LEN {
+            return Err(XAddressError::InvalidLength(data.len()));
+        }
+
+        let is_test = match [data[0], data[1]] {
+            MAINNET_PREFIX => false,
+            TESTNET_PREFIX => true,
+            _ => return Err(XAddressError::InvalidPrefix),
+        };
+
+        let mut account_id = [0u8; 20];
+        account_id.copy_from_slice(&data[2..22]);
+
+        let tag_value = u32::from_le_bytes([data[23], data[24], data[25], data[26]]);
+        if data[27..31] != [0u8; 4] {
+            return Err(XAddressError::UnsupportedTag);
+        }
+
+        let tag = match data[22] {
+            0 if tag_value == 0 => None,
+            0 => return Err(XAddressError::InvalidTagFlag(0)),
+            1 => Some(tag_value),
+            flag => return Err(XAddressError::InvalidTagFlag(flag)),
+        };
+
+        Ok(XAddress {
+            classic_address: codec::encode_account_id(&account_id),
+            tag,
+            is_test,
+        })
+    }
+}
+
+/// Проверяет, похожа ли строка на X-адрес (по первому символу)
+pub fn is_x_address(address: &str) -> bool {
+    address.starts_with('X') || address.starts_with('T')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Примеры из спецификации XLS-5d
+    const CLASSIC: &str = "r9cZA1mLK5R5Am25ArfXFmqgNwjZgnfk59";
+    const VECTORS: [(Option<u32>, bool, &str); 5] = [
+        (None, false, "X7AcgcsBL6XDcUb289X4mJ8djcdyKaB5hJDWMArnXr61cqZ"),
+        (Some(1), false, "X7AcgcsBL6XDcUb289X4mJ8djcdyKaGZMhc9YTE92ehJ2Fu"),
+        (Some(14), false, "X7AcgcsBL6XDcUb289X4mJ8djcdyKaGo2K5VpXpmCqbV2gS"),
+        (Some(11747), false, "X7AcgcsBL6XDcUb289X4mJ8djcdyKaLFuhLRuNXPrDeJd9A"),
+        (None, true, "T719a5UwUCnEs54UsxG9CJYYDhwmFCqkr7wxCcNcfZ6p5GZ"),
+    ];
+
+    #[test]
+    fn encodes_spec_vectors() {
+        for (tag, is_test, expected) in VECTORS {
+            let x_address = XAddress { classic_address: CLASSIC.to_string(), tag, is_test };
+            assert_eq!(x_address.encode(), expected, "тег {:?}", tag);
+        }
+    }
+
+    #[test]
+    fn decodes_spec_vectors() {
+        for (tag, is_test, x_address) in VECTORS {
+            let decoded = XAddress::decode(x_address).unwrap();
+            assert_eq!(decoded, XAddress { classic_address: CLASSIC.to_string(), tag, is_test });
+        }
+    }
+
+    #[test]
+    fn encodes_genesis_account() {
+        let x_address = XAddress {
+            classic_address: "rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh".to_string(),
+            tag: None,
+            is_test: false,
+        };
+        assert_eq!(x_address.encode(), "XVPcpSm47b1CZkf5AkKM9a84dQHe3m4sBhsrA4XtnBECTAc");
+    }
+
+    #[test]
+    fn round_trips_maximum_tag() {
+        let x_address = XAddress { classic_address: CLASSIC.to_string(), tag: Some(u32::MAX), is_test: true };
+        assert_eq!(XAddress::decode(&x_address.encode()).unwrap(), x_address);
+    }
+
+    #[test]
+    fn rejects_classic_address() {
+        assert!(matches!(XAddress::decode(CLASSIC), Err(XAddressError::InvalidLength(21))));
+    }
+
+    #[test]
+    fn rejects_unknown_prefix() {
+        let mut data = vec![0x05, 0x45];
+        data.extend_from_slice(&[0u8; 29]);
+        assert!(matches!(
+            XAddress::decode(&codec::encode_check(&data)),
+            Err(XAddressError::InvalidPrefix)
+        ));
+    }
+
+    #[test]
+    fn rejects_64_bit_tags() {
+        let mut data = MAINNET_PREFIX.to_vec();
+        data.extend_from_slice(&[0u8; 20]);
+        data.push(1);
+        data.extend_from_slice(&[0, 0, 0, 0, 1, 0, 0, 0]);
+        assert!(matches!(
+            XAddress::decode(&codec::encode_check(&data)),
+            Err(XAddressError::UnsupportedTag)
+        ));
+    }
+
+    #[test]
+    fn detects_x_address_prefix() {
+        assert!(is_x_address("X7AcgcsBL6XDcUb289X4mJ8djcdyKaB5hJDWMArnXr61cqZ"));
+        assert!(is_x_address("T719a5UwUCnEs54UsxG9CJYYDhwmFCqkr7wxCcNcfZ6p5GZ"));
+        assert!(!is_x_address(CLASSIC));
+    }
+}